@@ -52,7 +52,7 @@ impl Runner for Aoc2023_01 {
 
         for line in &self.lines {
             let matches = ac.find_overlapping_iter(line).collect::<Vec<_>>();
-            let first = matches.get(0).unwrap().pattern().as_i32() / 2 + 1;
+            let first = matches.first().unwrap().pattern().as_i32() / 2 + 1;
             let last = matches.iter().last().unwrap().pattern().as_i32() / 2 + 1;
 
             total += (10 * first + last) as i64;
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
-use aoclib::{get_repo_root, Runner};
+use aoclib::{get_repo_root, uints, Runner};
 
 #[derive(Default)]
 pub struct Aoc2023_04 {
@@ -29,8 +29,7 @@ impl Runner for Aoc2023_04 {
 
         for line in lines {
             let (card, data) = line.split_once(": ").unwrap();
-            let (_, num) = card.split_once(' ').unwrap();
-            let num = num.trim().parse::<i32>().unwrap();
+            let num = uints(card).next().unwrap();
             let (winners, numbers) = data.split_once(" | ").unwrap();
             self.cards.push(Card::new(num, winners, numbers));
         }
@@ -96,11 +95,7 @@ impl Card {
         }
     }
     fn parse(input: &str) -> Vec<i32> {
-        input
-            .split_whitespace()
-            .filter(|num|!num.is_empty())
-            .map(|num|num.parse::<i32>().unwrap())
-            .collect::<Vec<i32>>()
+        uints(input).collect()
     }
     fn winning_numbers(&self) -> i32 {
         self.winners
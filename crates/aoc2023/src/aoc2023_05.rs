@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
-use aoclib::{get_repo_root, Runner};
+use aoclib::{get_repo_root, ints, ints_n, Runner};
 use itertools::Itertools;
 
 #[derive(Default)]
@@ -99,13 +99,7 @@ fn apply_range_mapping(ranges: &mut Vec<(i64, i64)>, map: &Mapping) -> Vec<(i64,
 }
 
 fn parse_seeds(seeds: String) -> Vec<i64> {
-    seeds
-        .split_once(": ")
-        .unwrap()
-        .1
-        .split_whitespace()
-        .map(|num| num.parse::<i64>().unwrap())
-        .collect()
+    ints(&seeds).collect()
 }
 
 fn parse_maps(mut groups: Vec<String>) -> HashMap<String, Mapping> {
@@ -165,13 +159,8 @@ impl From<(i64,i64,i64)> for MapRange {
 }
 impl From<&String> for MapRange {
     fn from(value: &String) -> Self {
-        MapRange::from(
-            value
-                .split_whitespace()
-                .map(|num| num.parse::<i64>().unwrap())
-                .collect_tuple::<(i64,i64,i64)>()
-                .unwrap()
-        )
+        let [dest, src, range] = ints_n::<3>(value);
+        MapRange::from((dest, src, range))
     }
 }
 
@@ -1,5 +1,5 @@
 use std::path::PathBuf;
-use aoclib::{get_repo_root, Runner};
+use aoclib::{get_repo_root, uints, Runner};
 
 #[derive(Default)]
 pub struct Aoc2023_06 {
@@ -45,13 +45,7 @@ impl Runner for Aoc2023_06 {
 }
 
 fn get_numbers(str: &str) -> Vec<u64> {
-    str
-        .split_once(':')
-        .unwrap()
-        .1
-        .split_whitespace()
-        .map(|num| num.trim().parse::<u64>().unwrap())
-        .collect::<Vec<_>>()
+    uints(str).collect()
 }
 
 #[derive(Debug, Default, Clone)]
@@ -85,6 +85,7 @@ impl<T: Eq + Ord + Ranking + Hash + WildCard> PartialOrd for Hand<T> {
 
 trait Ranking {
     fn get_rank(&self) -> u32;
+    #[allow(dead_code)]
     fn get_result(&self) -> &str;
 }
 
@@ -210,7 +211,7 @@ impl<T> Hand<T> where T: Eq + Hash + Ranking + WildCard {
             true
         } else if wilds == 2 {
             // 2 wilds + a pair will always be a full house
-            return self.cards.iter().filter(|card| !card.is_wildcard()).combinations(2).any(|combination| combination[0] == combination[1]);
+            self.cards.iter().filter(|card| !card.is_wildcard()).combinations(2).any(|combination| combination[0] == combination[1])
         } else if wilds == 1 {
             self.cards
                 .iter()
@@ -222,7 +223,7 @@ impl<T> Hand<T> where T: Eq + Hash + Ranking + WildCard {
         } else {
             let rank_counts = self.rank_counts();
 
-            return rank_counts.values().any(|&count| count == 3) && rank_counts.values().any(|&count| count == 2);
+            rank_counts.values().any(|&count| count == 3) && rank_counts.values().any(|&count| count == 2)
         }
     }
 
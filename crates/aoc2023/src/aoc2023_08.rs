@@ -1,12 +1,17 @@
-use std::collections::HashMap;
 use std::path::PathBuf;
-use aoclib::{get_repo_root, lcm_of, Runner};
+use itertools::Itertools;
+use aoclib::{get_repo_root, Runner};
+use aoclib::dot::Dot;
+use aoclib::intern::{Graph, GraphBuilder};
+use aoclib::number_theory::crt;
+use aoclib::visualize::Colour;
 
 #[derive(Default)]
 pub struct Aoc2023_08 {
     input: PathBuf,
     instructions: Vec<char>,
-    nodes: HashMap<String, (String, String)>
+    nodes: Graph<char>,
+    z_nodes: Vec<bool>
 }
 
 impl Aoc2023_08 {
@@ -14,12 +19,30 @@ impl Aoc2023_08 {
         Self::default()
     }
 
-    pub fn get(&self, current: &String, count: i64) -> &String {
+    pub fn get(&self, current: u32, count: i64) -> u32 {
         let instruction = self.instructions[count as usize % self.instructions.len()];
-        match instruction {
-            'L' => &self.nodes.get(current).unwrap().0,
-            'R' => &self.nodes.get(current).unwrap().1,
-            _ => panic!("Invalid instruction")
+        self.nodes.follow(current, &instruction).expect("Invalid instruction")
+    }
+
+    fn find_cycle(&self, start: u32) -> Ghost {
+        // A walk's state is its node and its position in the instructions, indexed densely
+        let mut seen: Vec<Option<i64>> = vec![None; self.nodes.node_count() * self.instructions.len()];
+        let mut hits = vec![];
+        let mut current = start;
+        let mut count: i64 = 0;
+
+        loop {
+            let key = current as usize * self.instructions.len() + count as usize % self.instructions.len();
+            if let Some(offset) = seen[key] {
+                return Ghost { offset, period: count - offset, hits };
+            }
+            seen[key] = Some(count);
+
+            if self.z_nodes[current as usize] {
+                hits.push(count);
+            }
+            current = self.get(current, count);
+            count += 1;
         }
     }
 }
@@ -37,17 +60,22 @@ impl Runner for Aoc2023_08 {
         let mut lines = aoclib::read_lines(&self.input);
         self.instructions = lines.remove(0).chars().collect();
 
+        let mut builder = GraphBuilder::new();
         lines.iter().for_each(|line| {
             let (key, rest) = line.split_once(" = ").unwrap();
             let (left, right) = rest.trim_matches(|c| c == '(' || c == ')').split_once(", ").unwrap();
-            self.nodes.insert(key.to_string(), (left.to_string(), right.to_string()));
+            builder.add_edge(key, left, 'L').add_edge(key, right, 'R');
         });
+
+        self.nodes = builder.build();
+        self.z_nodes = self.nodes.nodes().map(|(_, label)| label.ends_with('Z')).collect();
     }
 
     fn part1(&mut self) -> i64 {
         let mut steps: i64 = 0;
-        let mut current = &"AAA".to_string();
-        while current != "ZZZ" {
+        let mut current = self.nodes.id("AAA").expect("No AAA node");
+        let end = self.nodes.id("ZZZ").expect("No ZZZ node");
+        while current != end {
             current = self.get(current, steps);
 
             steps += 1;
@@ -56,22 +84,77 @@ impl Runner for Aoc2023_08 {
     }
 
     fn part2(&mut self) -> i64 {
-        let mut counts: Vec<i64> = vec![];
+        let ghosts = self.nodes
+            .nodes()
+            .filter(|(_, label)| label.ends_with('A'))
+            .map(|(start, _)| self.find_cycle(start))
+            .collect::<Vec<Ghost>>();
 
-        for entry in self.nodes.keys().filter(|item| item.ends_with('A')).collect::<Vec<&String>>() {
-            let mut current = entry;
-            let mut count: i64 = 0;
+        earliest_common_hit(&ghosts).expect("Ghosts never reach Z nodes at the same time")
+    }
 
-            while !current.ends_with('Z') {
-                current = self.get(current, count);
-                count += 1;
-            }
-            counts.push(count);
-        }
-        lcm_of(counts)
+    fn graph(&self) -> Option<Dot> {
+        let mut dot = Dot::from_graph("2023-08", &self.nodes);
+        let labels: Vec<&str> = self.nodes.nodes().map(|(_, label)| label).collect();
+        dot.highlight(labels.iter().copied().filter(|label| label.ends_with('A')), Colour::Green)
+            .highlight(labels.iter().copied().filter(|label| label.ends_with('Z')), Colour::Red);
+        Some(dot)
     }
 }
 
+/// A ghost's walk: it starts repeating itself after `offset` steps, every `period` steps.
+/// `hits` holds every step before `offset + period` on which it stands on a Z node.
+#[derive(Debug)]
+struct Ghost {
+    offset: i64,
+    period: i64,
+    hits: Vec<i64>
+}
+
+impl Ghost {
+    fn is_hit(&self, step: i64) -> bool {
+        let step = if step < self.offset {
+            step
+        } else {
+            self.offset + (step - self.offset) % self.period
+        };
+        self.hits.contains(&step)
+    }
+
+    fn cyclic_hits(&self) -> Vec<i64> {
+        self.hits.iter().copied().filter(|&hit| hit >= self.offset).collect()
+    }
+}
+
+fn earliest_common_hit(ghosts: &[Ghost]) -> Option<i64> {
+    // Hits before a ghost enters its cycle only happen once, so check them directly
+    let early = ghosts
+        .iter()
+        .flat_map(|ghost| ghost.hits.iter().filter(|&&hit| hit < ghost.offset))
+        .filter(|&&hit| ghosts.iter().all(|ghost| ghost.is_hit(hit)))
+        .min()
+        .copied();
+
+    if early.is_some() {
+        return early;
+    }
+
+    // Every later hit repeats, so solve each combination of hits with the CRT
+    let min_step = ghosts.iter().map(|ghost| ghost.offset).max().unwrap_or(0);
+
+    ghosts
+        .iter()
+        .map(|ghost| ghost.cyclic_hits().into_iter().map(|hit| (hit, ghost.period)).collect::<Vec<_>>())
+        .multi_cartesian_product()
+        .filter_map(|congruences| crt(congruences).ok())
+        .map(|(step, modulus)| if step < min_step {
+            step + (min_step - step + modulus - 1) / modulus * modulus
+        } else {
+            step
+        })
+        .min()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,4 +180,15 @@ mod tests {
 
         assert_eq!(6, result);
     }
+
+    #[test]
+    fn offset_cycles() {
+        // Neither ghost's first Z hit equals its period, so lcm_of(periods) would be wrong
+        let ghosts = [
+            Ghost { offset: 1, period: 3, hits: vec![2] },
+            Ghost { offset: 2, period: 4, hits: vec![3] }
+        ];
+
+        assert_eq!(Some(11), earliest_common_hit(&ghosts));
+    }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
-use aoclib::{get_repo_root, read_lines, Runner};
+use aoclib::{get_repo_root, ints, Input, Runner};
+use aoclib::poly::{next_value, previous_value, PolyError};
 
 #[derive(Default)]
 pub struct Aoc2023_09 {
@@ -23,76 +24,34 @@ impl Runner for Aoc2023_09 {
     }
 
     fn parse(&mut self) {
-        let lines = read_lines(&self.input);
-        self.numbers = lines
-            .iter()
-            .map(|line|
-                line
-                    .split_whitespace()
-                    .map(|num|
-                        num
-                            .parse::<i64>()
-                            .unwrap()
-                    ).collect::<Vec<i64>>()
-            ).collect();
+        let input = Input::load(&self.input);
+        self.numbers = input
+            .lines()
+            .map(|line| ints(line).collect())
+            .collect();
     }
 
     fn part1(&mut self) -> i64 {
-        self.numbers.iter().fold(Vec::new(), |mut acc, numbers| {
-            get_next_number(&mut acc, numbers);
-            acc
-        }).iter().sum()
+        self.numbers
+            .iter()
+            .map(|numbers| extrapolated(next_value(numbers)))
+            .sum()
     }
 
-
     fn part2(&mut self) -> i64 {
-        self.numbers.iter().fold(Vec::new(), |mut acc, numbers| {
-            get_previous_number(&mut acc, numbers);
-            acc
-        }).iter().sum()
-    }
-}
-
-fn get_previous_number(acc: &mut Vec<i64>, numbers: &[i64]) {
-    let mut tree = build_tree(numbers);
-
-    let mut first_item = 0i64;
-
-    for item in tree.iter_mut().rev() {
-        item.insert(0, item.first().unwrap() - first_item);
-        first_item = *item.first().unwrap();
-    }
-
-    acc.push(first_item)
-}
-
-fn build_tree(numbers: &[i64]) -> Vec<Vec<i64>>{
-    let mut current = numbers.to_vec();
-    let mut tree : Vec<Vec<i64>> = Vec::new();
-
-    tree.push(current.clone());
-
-    while !current.iter().all(|&x| x == 0) {
-        current = get_differences(&current);
-        tree.push(current.clone());
+        self.numbers
+            .iter()
+            .map(|numbers| extrapolated(previous_value(numbers)))
+            .sum()
     }
-    tree
 }
 
-fn get_next_number(acc: &mut Vec<i64>, numbers: &[i64]) {
-    let mut tree = build_tree(numbers);
-
-    let mut last_item = 0i64;
-    for item in tree.iter_mut().rev() {
-        item.push(last_item + item.last().unwrap());
-        last_item = *item.last().unwrap();
+fn extrapolated(value: Result<i64, PolyError>) -> i64 {
+    match value {
+        Ok(value) => value,
+        Err(PolyError::Empty) => 0,
+        Err(error) => panic!("Unable to extrapolate: {error}"),
     }
-
-    acc.push(last_item);
-}
-
-fn get_differences(numbers: &[i64]) -> Vec<i64> {
-    numbers.windows(2).map(|w| w[1] - w[0]).collect()
 }
 
 
@@ -121,5 +80,12 @@ mod tests {
 
         assert_eq!(2, result);
     }
-}
 
+    #[test]
+    fn short_sequences() {
+        assert_eq!(5, extrapolated(next_value(&[1, 3])));
+        assert_eq!(-1, extrapolated(previous_value(&[1, 3])));
+        assert_eq!(4, extrapolated(next_value(&[4])));
+        assert_eq!(0, extrapolated(next_value(&[])));
+    }
+}
@@ -1,8 +1,10 @@
 use std::collections::HashSet;
-use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
 use itertools::Itertools;
-use aoclib::{get_repo_root, read_lines, Runner};
+use aoclib::{get_repo_root, read_full, Runner};
+use aoclib::geometry::polygon;
+use aoclib::grid::{parse_grid, GridCell};
+use aoclib::visualize::{Colour, Frame, Frames, Visualize};
 
 #[derive(Default)]
 pub struct Aoc2023_10 {
@@ -10,7 +12,8 @@ pub struct Aoc2023_10 {
     maze: Vec<Vec<PipeShape>>,
     pipe: Vec<(usize, usize)>,
     visited: HashSet<(usize, usize)>,
-    inside_loop: HashSet<(usize, usize)>
+    inside_loop: HashSet<(usize, usize)>,
+    frames: Frames
 }
 
 impl Aoc2023_10 {
@@ -27,20 +30,24 @@ impl Aoc2023_10 {
     }
 
 
-    fn _draw_maze(&self) {
+    fn maze_frame(&self, title: &str, show_inside: bool) -> Frame {
+        let mut frame = Frame::new(title);
+
         self.maze.iter().enumerate().for_each(|(row_index, row) | {
-            row.iter().enumerate().for_each(|(col_index, shape)| {
-                if self.visited.contains(&(row_index, col_index)) {
-                    print!("{}", shape);
-                } else if self.inside_loop.contains(&(row_index, col_index)) {
-                    print!("I");
-                    // print!("({},{})", row_index, col_index);
+            frame.push_row(row.iter().enumerate().map(|(col_index, shape)| {
+                if self.pipe.first() == Some(&(row_index, col_index)) {
+                    (shape.display_char(), Colour::Yellow)
+                } else if self.visited.contains(&(row_index, col_index)) {
+                    (shape.display_char(), Colour::Green)
+                } else if show_inside && self.inside_loop.contains(&(row_index, col_index)) {
+                    ('I', Colour::Red)
                 } else {
-                    print!(".");
+                    ('.', Colour::Grey)
                 }
-            });
-            println!();
-        })
+            }).collect());
+        });
+
+        frame
     }
 
     fn get_starting_shape(&self) -> PipeShape {
@@ -85,13 +92,9 @@ impl Aoc2023_10 {
     }
 
     fn compute_inside(&mut self) {
-        // println!("Computing what tiles are inside");
         let mut inside = false;
-        let line_entered_from = Direction::North;
-
         let mut inside_loop = HashSet::new();
 
-        // println!("Starting outside");
         self.maze.iter().enumerate().for_each(|(row_index, row) | {
             row.iter().enumerate().for_each(|(col_index, shape)| {
                 if self.visited.contains(&(row_index, col_index)) {
@@ -99,48 +102,36 @@ impl Aoc2023_10 {
                     if shape == PipeShape::StartingPosition {
                         shape = self.get_starting_shape();
                     }
+                    // Only pipes reaching south cross a ray running just below the row's centre
                     match shape {
-                        PipeShape::NorthSouth => inside = !inside,
-                        PipeShape::WestEast => {}
-                        PipeShape::SouthWest => {
-                            if line_entered_from == Direction::North {
-                                inside = !inside
-                            }
-                        },
-                        PipeShape::NorthWest => {
-                            if line_entered_from == Direction::South {
-                                inside = !inside
-                            }
-                        }
-                        PipeShape::SouthEast => {
-                            if line_entered_from == Direction::North {
-                                inside = !inside
-                            }
-                        }
-                        PipeShape::NorthEast => {
-                            if line_entered_from == Direction::South {
-                                inside = !inside
-                            }
-                        }
-                        PipeShape::StartingPosition => {
-                        }
-                        PipeShape::Blank => {}
+                        PipeShape::NorthSouth | PipeShape::SouthWest | PipeShape::SouthEast => inside = !inside,
+                        PipeShape::WestEast
+                        | PipeShape::NorthWest
+                        | PipeShape::NorthEast
+                        | PipeShape::StartingPosition
+                        | PipeShape::Blank => {}
                     }
                 }
                 if inside {
                     inside_loop.insert((row_index, col_index));
                 }
-
             });
-            if inside {
-                println!("Error on row: {}", row_index);
-            }
             inside = false;
         });
 
         self.inside_loop = inside_loop;
     }
 
+    fn count_inside_pick(&self) -> i64 {
+        // Every tile of the loop is a vertex, so the boundary holds exactly pipe.len() lattice points
+        let vertices = self.pipe
+            .iter()
+            .map(|&(row, col)| (row as i64, col as i64))
+            .collect::<Vec<_>>();
+
+        polygon::interior_points(&vertices)
+    }
+
     fn get_shape(&self, pos: (usize, usize)) -> &PipeShape {
         self.maze.get(pos.0).unwrap().get(pos.1).unwrap()
     }
@@ -175,41 +166,29 @@ impl Aoc2023_10 {
 
         // get above
         if let Some(row) = self.maze.get(pos.0.wrapping_sub(1)) {
-            if let Some(shape) = row.get(pos.1) {
-                match shape {
-                    PipeShape::StartingPosition | PipeShape::SouthEast | PipeShape::SouthWest | PipeShape::NorthSouth => result.push((pos.0.wrapping_sub(1), pos.1)),
-                    _ => {}
-                }
+            if let Some(PipeShape::StartingPosition | PipeShape::SouthEast | PipeShape::SouthWest | PipeShape::NorthSouth) = row.get(pos.1) {
+                result.push((pos.0.wrapping_sub(1), pos.1));
             }
         }
 
         // get below
         if let Some(row) = self.maze.get(pos.0 + 1) {
-            if let Some(shape) = row.get(pos.1) {
-                match shape {
-                    PipeShape::StartingPosition | PipeShape::NorthEast | PipeShape::NorthWest | PipeShape::NorthSouth => result.push((pos.0+1, pos.1)),
-                    _ => {}
-                }
+            if let Some(PipeShape::StartingPosition | PipeShape::NorthEast | PipeShape::NorthWest | PipeShape::NorthSouth) = row.get(pos.1) {
+                result.push((pos.0+1, pos.1));
             }
         }
 
         //get left
         if let Some(row) = self.maze.get(pos.0) {
-            if let Some(shape) = row.get(pos.1.wrapping_sub(1)) {
-                match shape {
-                    PipeShape::StartingPosition | PipeShape::NorthEast | PipeShape::SouthEast | PipeShape::WestEast => result.push((pos.0, pos.1.wrapping_sub(1))),
-                    _ => {}
-                }
+            if let Some(PipeShape::StartingPosition | PipeShape::NorthEast | PipeShape::SouthEast | PipeShape::WestEast) = row.get(pos.1.wrapping_sub(1)) {
+                result.push((pos.0, pos.1.wrapping_sub(1)));
             }
         }
 
         // get right
         if let Some(row) = self.maze.get(pos.0) {
-            if let Some(shape) = row.get(pos.1+1) {
-                match shape {
-                    PipeShape::StartingPosition | PipeShape::NorthWest | PipeShape::SouthWest | PipeShape::WestEast=> result.push((pos.0, pos.1+1)),
-                    _ => {}
-                }
+            if let Some(PipeShape::StartingPosition | PipeShape::NorthWest | PipeShape::SouthWest | PipeShape::WestEast) = row.get(pos.1+1) {
+                result.push((pos.0, pos.1+1));
             }
         }
 
@@ -227,14 +206,7 @@ impl Runner for Aoc2023_10 {
     }
 
     fn parse(&mut self) {
-        self.maze = read_lines(&self.input)
-            .iter()
-            .map(|line|
-                line
-                    .chars()
-                    .map(PipeShape::from)
-                    .collect()
-            ).collect();
+        self.maze = parse_grid(&read_full(&self.input)).expect("Unknown shape in maze");
 
         match self.find_starting_pos() {
             Some((row_index, col_index)) => {
@@ -266,16 +238,34 @@ impl Runner for Aoc2023_10 {
             }
         }
         self.compute_inside();
-        // self.draw_maze();
     }
 
     fn part1(&mut self) -> i64 {
+        if self.frames.is_enabled() {
+            self.frames.emit(self.maze_frame("Loop", false));
+        }
         self.pipe.len() as i64 / 2
     }
 
     fn part2(&mut self) -> i64 {
         let results: HashSet<(usize, usize)> = self.inside_loop.difference(&self.visited).cloned().collect();
-        results.len() as i64
+        let scanline = results.len() as i64;
+
+        debug_assert_eq!(scanline, self.count_inside_pick(), "Scanline and Pick's theorem disagree");
+        if self.frames.is_enabled() {
+            self.frames.emit(self.maze_frame("Inside the loop", true));
+        }
+        scanline
+    }
+
+    fn visualize(&mut self) -> Option<&mut dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Aoc2023_10 {
+    fn frames(&mut self) -> &mut Frames {
+        &mut self.frames
     }
 }
 
@@ -287,16 +277,24 @@ enum Direction {
     West
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(PartialEq, Debug, Clone, Copy, GridCell)]
 enum PipeShape {
-    NorthSouth, // |
-    WestEast, // -
-    SouthWest, // 7
-    NorthWest, // J
-    SouthEast, // F
-    NorthEast, // L
-    StartingPosition, // S,
-    Blank, // .
+    #[cell('|', display = '│')]
+    NorthSouth,
+    #[cell('-', display = '─')]
+    WestEast,
+    #[cell('7', display = '┐')]
+    SouthWest,
+    #[cell('J', display = '┘')]
+    NorthWest,
+    #[cell('F', display = '┌')]
+    SouthEast,
+    #[cell('L', display = '└')]
+    NorthEast,
+    #[cell('S')]
+    StartingPosition,
+    #[cell('.')]
+    Blank,
 }
 
 impl PipeShape {
@@ -311,37 +309,6 @@ impl PipeShape {
     }
 }
 
-impl From<char> for PipeShape {
-    fn from(value: char) -> Self {
-        match value {
-            '|' => PipeShape::NorthSouth,
-            '-' => PipeShape::WestEast,
-            '7' => PipeShape::SouthWest,
-            'J' => PipeShape::NorthWest,
-            'F' => PipeShape::SouthEast,
-            'L' => PipeShape::NorthEast,
-            'S' => PipeShape::StartingPosition,
-            '.' => PipeShape::Blank,
-            _ => panic!("Unknown Shape")
-        }
-    }
-}
-
-impl Display for PipeShape {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            PipeShape::StartingPosition => write!(f, "S"),
-            PipeShape::NorthSouth => write!(f, "│"),
-            PipeShape::WestEast => write!(f, "─"),
-            PipeShape::SouthWest => write!(f, "┐"),
-            PipeShape::NorthWest => write!(f, "┘"),
-            PipeShape::SouthEast => write!(f, "┌"),
-            PipeShape::NorthEast => write!(f, "└"),
-            PipeShape::Blank => write!(f, "."),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,4 +334,16 @@ mod tests {
 
         assert_eq!(10, result);
     }
+
+    #[test]
+    fn pick_matches_scanline() {
+        for file in ["crates/aoc2023/test/2023-10.txt", "crates/aoc2023/test/2023-10b.txt"] {
+            let mut day = Aoc2023_10::new();
+
+            day.set_input(file);
+            day.parse();
+
+            assert_eq!(day.part2(), day.count_inside_pick());
+        }
+    }
 }
\ No newline at end of file
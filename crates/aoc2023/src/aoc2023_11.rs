@@ -17,8 +17,7 @@ impl Aoc2023_11 {
         Self::default()
     }
 
-    fn get_galaxy_positions(&self) -> HashSet<(usize, usize)> {
-        let inflation = self.inflation.unwrap_or(1);
+    fn get_galaxy_positions(&self, inflation: usize) -> HashSet<(usize, usize)> {
         let mut galaxies: HashSet<(usize, usize)> = HashSet::new();
 
         self.map.iter().enumerate().for_each(|(row_index, row)| {
@@ -33,13 +32,14 @@ impl Aoc2023_11 {
         galaxies
     }
 
-    fn get_distances(&self) -> i64 {
-        self.get_galaxy_positions().iter().combinations(2).map(|pair| {
+    fn get_distances(&self, inflation: usize) -> i64 {
+        self.get_galaxy_positions(inflation).iter().combinations(2).map(|pair| {
             let (a, b) = pair.iter().cloned().collect_tuple().unwrap();
             manhattan_distance(a, b)
         }).sum::<usize>() as i64
     }
 
+    #[cfg(test)]
     fn set_inflation(&mut self, inflation: usize) {
         self.inflation = Some(inflation);
     }
@@ -78,19 +78,11 @@ impl Runner for Aoc2023_11 {
     }
 
     fn part1(&mut self) -> i64 {
-        if self.inflation.is_none() {
-            self.set_inflation(2);
-        }
-
-        self.get_distances()
+        self.get_distances(self.inflation.unwrap_or(2))
     }
 
     fn part2(&mut self) -> i64 {
-        if self.inflation.is_none() {
-            self.set_inflation(1_000_000);
-        }
-
-        self.get_distances()
+        self.get_distances(self.inflation.unwrap_or(1_000_000))
     }
 }
 
@@ -98,8 +90,8 @@ fn manhattan_distance(a: &(usize, usize), b: &(usize, usize)) -> usize {
     let (x1, y1) = *a;
     let (x2, y2) = *b;
 
-    let dx = if x1 > x2 { x1 - x2 } else { x2 - x1 };
-    let dy = if y1 > y2 { y1 - y2 } else { y2 - y1 };
+    let dx = x1.abs_diff(x2);
+    let dy = y1.abs_diff(y2);
 
     dx + dy
 }
@@ -131,4 +123,15 @@ mod tests {
 
         assert_eq!(8410, result);
     }
+
+    #[test]
+    fn parts_share_an_instance() {
+        let mut day = Aoc2023_11::new();
+
+        day.set_input("crates/aoc2023/test/2023-11.txt");
+        day.parse();
+
+        assert_eq!(374, day.part1());
+        assert_eq!(82000210, day.part2());
+    }
 }
\ No newline at end of file
@@ -1,9 +1,11 @@
-use std::collections::HashMap;
 use std::path::PathBuf;
 use std::str::FromStr;
 use aoclib::{get_repo_root, Runner};
+use aoclib::memo::Memo;
 
-type Cache = HashMap<(Vec<char>, Vec<usize>), usize>;
+// The recursion only ever looks at suffixes of the pattern and sizes, so their remaining
+// lengths identify a call without copying either slice.
+type Cache = Memo<(usize, usize), usize>;
 
 #[derive(Default)]
 pub struct Aoc2023_12 {
@@ -53,13 +55,14 @@ impl Spring {
         Spring { pattern: p.chars().collect(), sizes: s}
     }
     fn combos(&self) -> i64 {
-        let mut cache = HashMap::new();
+        let mut cache = Memo::new();
         Self::do_score(&self.pattern, &self.sizes, &mut cache) as i64
     }
 
     fn do_score(pattern: &[char], sizes: &[usize], cache: &mut Cache) -> usize {
-        if let Some(result) = cache.get(&(pattern.to_vec(), sizes.to_vec())) {
-            return *result;
+        let key = (pattern.len(), sizes.len());
+        if let Some(result) = cache.get(&key) {
+            return result;
         }
         if sizes.is_empty() {
             return (!pattern.contains(&'#')) as usize
@@ -79,8 +82,7 @@ impl Spring {
             _ => panic!("Invalid char in input")
         };
 
-        cache.insert((pattern.to_vec(), sizes.to_vec()), result);
-        result
+        cache.insert(key, result)
     }
 
     fn do_hash(pattern: &[char], sizes: &[usize], cache: &mut Cache) -> usize {
@@ -3,17 +3,27 @@ use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
 use std::str::FromStr;
 use aoclib::{get_repo_root, Runner};
+use aoclib::grid::{parse_grid, GridCell, UnknownCell};
+use aoclib::render::Palette;
+use aoclib::visualize::{Colour, Frame, Frames, Visualize};
 
 #[derive(Default)]
 pub struct Aoc2023_14 {
     input: PathBuf,
-    map: Rocks
+    map: Rocks,
+    frames: Frames
 }
 
 impl Aoc2023_14 {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn emit_frame(&mut self, title: &str) {
+        if self.frames.is_enabled() {
+            self.frames.emit(Frame::from_grid(title, &self.map.map, Rock::colour));
+        }
+    }
 }
 
 impl Runner for Aoc2023_14 {
@@ -30,7 +40,9 @@ impl Runner for Aoc2023_14 {
     }
 
     fn part1(&mut self) -> i64 {
+        self.emit_frame("Start");
         self.map.tilt(Direction::North);
+        self.emit_frame("Tilted north");
         self.map.load_total()
     }
 
@@ -45,6 +57,7 @@ impl Runner for Aoc2023_14 {
             for dir in DIRS {
                 self.map.tilt(dir);
             }
+            self.emit_frame(&format!("Cycle {i}"));
 
             if let Some(val) = loop_detector.insert(self.map.clone(), i) {
                 break (val, i);
@@ -55,14 +68,25 @@ impl Runner for Aoc2023_14 {
         let remaining_loops = 1000000000 - start;
         let phase = remaining_loops % difference;
 
-        for _ in 0..phase {
+        for i in 0..phase {
             for dir in DIRS {
                 self.map.tilt(dir);
             }
+            self.emit_frame(&format!("Cycle {}", end + i + 1));
         }
 
         self.map.load_total()
     }
+
+    fn visualize(&mut self) -> Option<&mut dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Aoc2023_14 {
+    fn frames(&mut self) -> &mut Frames {
+        &mut self.frames
+    }
 }
 
 const DIRS: [Direction; 4] = [
@@ -80,21 +104,30 @@ enum Direction {
     East
 }
 
-#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+#[derive(Debug, PartialEq, Clone, Eq, Hash, GridCell)]
 enum Rock {
+    #[cell('#')]
     Cube,
+    #[cell('O')]
     Round,
+    #[cell('.')]
     Empty
 }
 
+impl Palette for Rock {
+    fn colour(&self) -> Colour {
+        match self {
+            Rock::Cube => Colour::Grey,
+            Rock::Round => Colour::Yellow,
+            Rock::Empty => Colour::Default
+        }
+    }
+}
+
 impl Display for Rocks {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.map.iter().for_each(|row| {
-            let s: String = row.iter().map(|rock| match rock {
-                Rock::Cube => '#',
-                Rock::Round => 'O',
-                Rock::Empty => '.'
-            }).collect();
+            let s: String = row.iter().map(Rock::display_char).collect();
             writeln!(f, "{s}").unwrap();
         });
 
@@ -181,21 +214,10 @@ impl Rocks {
 }
 
 impl FromStr for Rocks {
-    type Err = ();
+    type Err = UnknownCell;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map = Vec::new();
-        s.lines().for_each(|line| {
-            let row = line.chars().map(|c| match c {
-                '.' => Rock::Empty,
-                'O' => Rock::Round,
-                '#' => Rock::Cube,
-                _ => panic!("Invalid character: {c}")
-            }).collect();
-            map.push(row);
-        });
-
-        Ok(Rocks{ map })
+        Ok(Rocks{ map: parse_grid(s)? })
     }
 }
 
@@ -1,4 +1,4 @@
-use aoclib::{Runner, Selector};
+use aoclib::{Factory, Options};
 
 mod aoc2023_01;
 mod aoc2023_02;
@@ -32,62 +32,32 @@ use aoc2023_13::*;
 use aoc2023_14::*;
 use aoc2023_15::*;
 
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: aoclib::alloc::CountingAllocator = aoclib::alloc::CountingAllocator;
+
 fn main() {
-    run_2023(Selector::Last);
+    run_2023(&Options::from_env());
 }
 
-fn run_2023(which: Selector) {
-    let mut day01 = Aoc2023_01::new();
-    let mut day02 = Aoc2023_02::new();
-    let mut day03 = Aoc2023_03::new();
-    let mut day04 = Aoc2023_04::new();
-    let mut day05 = Aoc2023_05::new();
-    let mut day06 = Aoc2023_06::new();
-    let mut day07 = Aoc2023_07::new();
-    let mut day08 = Aoc2023_08::new();
-    let mut day09 = Aoc2023_09::new();
-    let mut day10 = Aoc2023_10::new();
-    let mut day11 = Aoc2023_11::new();
-    let mut day12 = Aoc2023_12::new();
-    let mut day13 = Aoc2023_13::new();
-    let mut day14 = Aoc2023_14::new();
-    let mut day15 = Aoc2023_15::new();
-
-    let mut days: Vec<&mut dyn Runner> = vec![
-        &mut day01,
-        &mut day02,
-        &mut day03,
-        &mut day04,
-        &mut day05,
-        &mut day06,
-        &mut day07,
-        &mut day08,
-        &mut day09,
-        &mut day10,
-        &mut day11,
-        &mut day12,
-        &mut day13,
-        &mut day14,
-        &mut day15
-
+fn run_2023(options: &Options) {
+    let days: Vec<Factory> = vec![
+        || Box::new(Aoc2023_01::new()),
+        || Box::new(Aoc2023_02::new()),
+        || Box::new(Aoc2023_03::new()),
+        || Box::new(Aoc2023_04::new()),
+        || Box::new(Aoc2023_05::new()),
+        || Box::new(Aoc2023_06::new()),
+        || Box::new(Aoc2023_07::new()),
+        || Box::new(Aoc2023_08::new()),
+        || Box::new(Aoc2023_09::new()),
+        || Box::new(Aoc2023_10::new()),
+        || Box::new(Aoc2023_11::new()),
+        || Box::new(Aoc2023_12::new()),
+        || Box::new(Aoc2023_13::new()),
+        || Box::new(Aoc2023_14::new()),
+        || Box::new(Aoc2023_15::new()),
     ];
 
-
-
-    match which {
-        Selector::Last => {
-            let last = days.len() - 1;
-            let d = &mut days[last];
-            aoclib::run_solution(*d);
-        }
-        Selector::All => {
-            for d in days {
-                aoclib::run_solution(d);
-            }
-        }
-        Selector::One(num) => {
-            let d = &mut days[num-1];
-            aoclib::run_solution(*d);
-        }
-    }
-}
\ No newline at end of file
+    aoclib::run(&days, options);
+}
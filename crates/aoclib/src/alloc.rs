@@ -0,0 +1,195 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// The counters a [`CountingAllocator`] keeps.
+struct Counters {
+    installed: AtomicBool,
+    allocations: AtomicUsize,
+    bytes: AtomicUsize,
+    live: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+static COUNTERS: Counters = Counters::new();
+
+impl Counters {
+    const fn new() -> Self {
+        Counters {
+            installed: AtomicBool::new(false),
+            allocations: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+            live: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    fn allocated(&self, size: usize) {
+        self.installed.store(true, Ordering::Relaxed);
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+        self.grew(size);
+    }
+
+    /// A reallocation isn't a new allocation, and only adds the bytes it grew by.
+    fn reallocated(&self, old_size: usize, new_size: usize) {
+        if new_size > old_size {
+            self.bytes.fetch_add(new_size - old_size, Ordering::Relaxed);
+            self.grew(new_size - old_size);
+        } else {
+            self.freed(old_size - new_size);
+        }
+    }
+
+    fn grew(&self, size: usize) {
+        let live = self.live.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(&self, size: usize) {
+        self.live.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    fn measure(&'static self) -> Measurement {
+        self.peak.store(self.live.load(Ordering::Relaxed), Ordering::Relaxed);
+        Measurement {
+            counters: self,
+            allocations: self.allocations.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A global allocator that counts what passes through it on the way to the system allocator.
+/// Binaries opt in with, usually behind a cargo feature so plain runs don't pay for the counting,
+///
+/// ```ignore
+/// #[cfg(feature = "alloc-stats")]
+/// #[global_allocator]
+/// static ALLOCATOR: aoclib::alloc::CountingAllocator = aoclib::alloc::CountingAllocator;
+/// ```
+///
+/// The counts are process wide, so they include anything other threads allocate meanwhile.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            COUNTERS.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            COUNTERS.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        COUNTERS.freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            COUNTERS.reallocated(layout.size(), new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether a [`CountingAllocator`] is the global allocator. This is only known once it has
+/// allocated something.
+pub fn is_installed() -> bool {
+    COUNTERS.installed.load(Ordering::Relaxed)
+}
+
+/// What was allocated between a [`Measurement`] starting and finishing.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    /// The most bytes live at once, including any that were live before it started.
+    pub peak: usize,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:>9} allocs {:>10} peak {:>10}", self.allocations, bytes_string(self.bytes), bytes_string(self.peak))
+    }
+}
+
+fn bytes_string(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// The counters at the start of a measurement.
+#[derive(Clone, Copy)]
+pub struct Measurement {
+    counters: &'static Counters,
+    allocations: usize,
+    bytes: usize,
+}
+
+impl Measurement {
+    /// Starts measuring, resetting the peak to what's live now.
+    pub fn start() -> Self {
+        COUNTERS.measure()
+    }
+
+    pub fn finish(self) -> AllocStats {
+        AllocStats {
+            allocations: self.counters.allocations.load(Ordering::Relaxed) - self.allocations,
+            bytes: self.counters.bytes.load(Ordering::Relaxed) - self.bytes,
+            peak: self.counters.peak.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!("512 B", bytes_string(512));
+        assert_eq!("1.5 KiB", bytes_string(1536));
+        assert_eq!("3.0 MiB", bytes_string(3 * 1024 * 1024));
+    }
+
+    #[test]
+    fn measures_allocations() {
+        // Counters of its own, so the global ones other tests might see stay untouched
+        static COUNTED: Counters = Counters::new();
+        COUNTED.allocated(1024);
+        let measurement = COUNTED.measure();
+
+        COUNTED.allocated(4096);
+        COUNTED.reallocated(4096, 8192);
+        COUNTED.freed(8192);
+        COUNTED.allocated(16);
+        COUNTED.reallocated(16, 8);
+
+        let stats = measurement.finish();
+        assert_eq!(AllocStats { allocations: 2, bytes: 8192 + 16, peak: 1024 + 8192 }, stats);
+        assert!(!is_installed());
+    }
+}
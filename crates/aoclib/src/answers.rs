@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::{get_repo_root, records};
+
+/// The accepted answers for one year, kept in `crates/aoc<year>/answers.txt` as lines of
+/// `<day> <part> <answer>`.
+#[derive(Debug, Default, Clone)]
+pub struct AnswerStore {
+    path: PathBuf,
+    answers: BTreeMap<(usize, usize), i64>,
+}
+
+pub fn store_path(year: usize) -> PathBuf {
+    get_repo_root().join(format!("aoc/crates/aoc{year}/answers.txt"))
+}
+
+impl AnswerStore {
+    /// Loads the store at `path`, which is empty if the file doesn't exist yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let answers = records::load(&path, "<day> <part> <answer>", |fields| match *fields {
+            [day, part, answer] => Some(((day.parse().ok()?, part.parse().ok()?), answer.parse().ok()?)),
+            _ => None,
+        })?;
+
+        Ok(AnswerStore { path, answers: answers.into_iter().collect() })
+    }
+
+    pub fn get(&self, day: usize, part: usize) -> Option<i64> {
+        self.answers.get(&(day, part)).copied()
+    }
+
+    pub fn record(&mut self, day: usize, part: usize, answer: i64) {
+        self.answers.insert((day, part), answer);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let lines = self.answers.iter().map(|((day, part), answer)| format!("{day} {part} {answer}"));
+        records::save(&self.path, "day part answer", lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+    use super::*;
+    use crate::testing::TempPath;
+
+    #[test]
+    fn saves_in_day_order() {
+        let path = TempPath::new("answers.txt");
+
+        let mut store = AnswerStore::load(&path).unwrap();
+        store.record(8, 2, 13133452426987);
+        store.record(1, 2, 281);
+        store.record(1, 1, -5);
+        store.save().unwrap();
+
+        assert_eq!("# day part answer\n1 1 -5\n1 2 281\n8 2 13133452426987\n", read_to_string(&path).unwrap());
+    }
+}
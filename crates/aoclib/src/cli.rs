@@ -0,0 +1,369 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::str::FromStr;
+use crate::guesses::GuessChecker;
+use crate::report::{BenchmarkReporter, ConsoleReporter, JsonReporter, MarkdownReporter, Observer};
+use crate::sandbox::Limits;
+use crate::Selector;
+use crate::visualize::TerminalRenderer;
+
+const USAGE: &str = "Usage: aoc [doctor | fetch | submit [--part <n>] [--answer <n>] [--force] | extract <html> [--yes] | readme [--runs <n>] [--reveal] [--from-store]] [all | <day>] [--example] [--visualize] [--delay <ms>] [--step] [--export <dir>] [--dot <dir>] [--timeout <secs>] [--sandbox] [--memory <MiB>] [--cpu <secs>] [--json <file>] [--markdown <file>] [--bench]";
+
+/// How long a phase may run before the runner gives up on it, unless `--timeout` says otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// What the runner was asked to do.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Run the selected days.
+    #[default]
+    Run,
+    /// Regenerate the progress section of the README.
+    Readme,
+    /// Check the registered days' inputs, examples and answers.
+    Doctor,
+    /// Download the selected days' inputs.
+    Fetch,
+    /// Submit one part of the selected day.
+    Submit,
+    /// Add the examples from a saved puzzle description to the test directory.
+    Extract,
+}
+
+/// How to build the README's progress section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadmeOptions {
+    /// How many times to run each day for its median timings.
+    pub runs: usize,
+    /// Show the answers themselves, not just the stars.
+    pub reveal: bool,
+    /// Take the stars from the answers store rather than running anything.
+    pub from_store: bool,
+}
+
+impl Default for ReadmeOptions {
+    fn default() -> Self {
+        ReadmeOptions { runs: 5, reveal: false, from_store: false }
+    }
+}
+
+/// What to submit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SubmitOptions {
+    /// The part to submit, or `None` for the first one without a recorded answer.
+    pub part: Option<usize>,
+    /// The answer to submit, or `None` to run the day for it.
+    pub answer: Option<i64>,
+    /// Submit even if earlier guesses rule the answer out.
+    pub force: bool,
+}
+
+/// Where to extract examples from.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExtractOptions {
+    /// The puzzle description, saved from the site.
+    pub html: Option<PathBuf>,
+    /// Write the examples without asking first.
+    pub yes: bool,
+}
+
+/// A reporter to run alongside the console output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Report {
+    Json(PathBuf),
+    Markdown(PathBuf),
+    Benchmark,
+}
+
+/// How the runner was asked to run, parsed from the command line.
+#[derive(Debug)]
+pub struct Options {
+    pub command: Command,
+    pub selector: Selector,
+    /// Run against the examples under the test directory rather than the puzzle input.
+    pub example: bool,
+    pub visualize: Option<TerminalRenderer>,
+    /// Where to write each day's frames as a GIF animation and a PNG of the final state.
+    pub export: Option<PathBuf>,
+    /// Where to write the parsed graph of days that have one, as Graphviz DOT.
+    pub dot: Option<PathBuf>,
+    /// The longest each phase may run, or `None` to wait forever, which `--timeout 0` asks for. A
+    /// phase that runs out of time can't be stopped, so its thread keeps running, and allocating,
+    /// until the process exits.
+    pub timeout: Option<Duration>,
+    /// Run each day in a child process with these limits.
+    pub sandbox: Option<Limits>,
+    pub reports: Vec<Report>,
+    pub readme: ReadmeOptions,
+    pub submit: SubmitOptions,
+    pub extract: ExtractOptions,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            command: Command::default(),
+            selector: Selector::default(),
+            example: false,
+            visualize: None,
+            export: None,
+            dot: None,
+            timeout: Some(DEFAULT_TIMEOUT),
+            sandbox: None,
+            reports: vec![],
+            readme: ReadmeOptions::default(),
+            submit: SubmitOptions::default(),
+            extract: ExtractOptions::default(),
+        }
+    }
+}
+
+impl Options {
+    /// Parses the process arguments, exiting with a usage message if they're invalid.
+    pub fn from_env() -> Self {
+        parse_args(std::env::args().skip(1)).unwrap_or_else(|error| {
+            eprintln!("{error}\n{USAGE}");
+            std::process::exit(2);
+        })
+    }
+
+    /// The console reporter and, for puzzle inputs, the guess checker, followed by each reporter
+    /// asked for. Reports whose file can't be created are left out with a warning.
+    pub fn observers(&self) -> Vec<Box<dyn Observer>> {
+        let mut observers: Vec<Box<dyn Observer>> = vec![Box::new(ConsoleReporter)];
+        if !self.example {
+            observers.push(Box::new(GuessChecker::default()));
+        }
+
+        for report in &self.reports {
+            let observer: Box<dyn Observer> = match report {
+                Report::Benchmark => Box::new(BenchmarkReporter::default()),
+                Report::Json(path) | Report::Markdown(path) => {
+                    let file = match File::create(path) {
+                        Ok(file) => BufWriter::new(file),
+                        Err(error) => {
+                            eprintln!("Unable to create {}: {error}", path.display());
+                            continue;
+                        }
+                    };
+                    match report {
+                        Report::Json(_) => Box::new(JsonReporter::new(file)),
+                        _ => Box::new(MarkdownReporter::new(file)),
+                    }
+                }
+            };
+            observers.push(observer);
+        }
+
+        observers
+    }
+
+    /// The arguments that make a child copy of the runner run just `day` the same way, without
+    /// the sandbox options. [`parse_args`] rejects the terminal-only and report options along
+    /// with `--sandbox`, so there are none of those to pass on.
+    pub fn child_args(&self, day: usize) -> Vec<String> {
+        let mut args = vec![day.to_string()];
+        if self.example {
+            args.push("--example".to_string());
+        }
+        let secs = self.timeout.map_or(0, |timeout| timeout.as_secs().max(1));
+        args.extend(["--timeout".to_string(), secs.to_string()]);
+        if let Some(dir) = &self.export {
+            args.extend(["--export".to_string(), dir.display().to_string()]);
+        }
+        if let Some(dir) = &self.dot {
+            args.extend(["--dot".to_string(), dir.display().to_string()]);
+        }
+        args
+    }
+}
+
+fn number<T: FromStr, I: Iterator<Item = String>>(args: &mut I, error: &str) -> Result<T, String> {
+    args.next().and_then(|n| n.parse().ok()).ok_or_else(|| error.to_string())
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "all" => options.selector = Selector::All,
+            "readme" => options.command = Command::Readme,
+            "doctor" => options.command = Command::Doctor,
+            "fetch" => options.command = Command::Fetch,
+            "submit" => options.command = Command::Submit,
+            "--part" => match number(&mut args, "--part needs 1 or 2")? {
+                part @ (1 | 2) => options.submit.part = Some(part),
+                _ => return Err("--part needs 1 or 2".to_string()),
+            },
+            "--answer" => options.submit.answer = Some(number(&mut args, "--answer needs a number")?),
+            "--force" => options.submit.force = true,
+            "extract" => {
+                options.command = Command::Extract;
+                options.extract.html = Some(args.next().ok_or("extract needs a saved puzzle description")?.into());
+            }
+            "--yes" => options.extract.yes = true,
+            "--example" => options.example = true,
+            "--runs" => options.readme.runs = number(&mut args, "--runs needs a number")?,
+            "--reveal" => options.readme.reveal = true,
+            "--from-store" => options.readme.from_store = true,
+            "--visualize" => {
+                options.visualize.get_or_insert_with(TerminalRenderer::default);
+            }
+            "--step" => options.visualize.get_or_insert_with(TerminalRenderer::default).step = true,
+            "--delay" => {
+                let ms = number(&mut args, "--delay needs a number of milliseconds")?;
+                options.visualize.get_or_insert_with(TerminalRenderer::default).delay = Duration::from_millis(ms);
+            }
+            "--export" => options.export = Some(args.next().ok_or("--export needs a directory")?.into()),
+            "--dot" => options.dot = Some(args.next().ok_or("--dot needs a directory")?.into()),
+            "--timeout" => {
+                let secs: u64 = number(&mut args, "--timeout needs a number of seconds")?;
+                options.timeout = (secs > 0).then(|| Duration::from_secs(secs));
+            }
+            "--sandbox" => {
+                options.sandbox.get_or_insert_with(Limits::default);
+            }
+            "--memory" => {
+                let mib = number(&mut args, "--memory needs a number of MiB")?;
+                options.sandbox.get_or_insert_with(Limits::default).memory_mib = mib;
+            }
+            "--cpu" => {
+                let secs = number(&mut args, "--cpu needs a number of seconds")?;
+                options.sandbox.get_or_insert_with(Limits::default).cpu_secs = secs;
+            }
+            "--json" => options.reports.push(Report::Json(args.next().ok_or("--json needs a file")?.into())),
+            "--markdown" => options.reports.push(Report::Markdown(args.next().ok_or("--markdown needs a file")?.into())),
+            "--bench" => options.reports.push(Report::Benchmark),
+            day => match day.parse() {
+                Ok(day) if day > 0 => options.selector = Selector::One(day),
+                _ => return Err(format!("Unknown argument: {arg}")),
+            },
+        }
+    }
+
+    // Sandboxed days run in child processes whose output is only printed once they're done, so
+    // nothing in the parent sees their phases
+    if options.sandbox.is_some() && options.visualize.is_some() {
+        return Err("--visualize can't be used with --sandbox".to_string());
+    }
+    if options.sandbox.is_some() && !options.reports.is_empty() {
+        return Err("--json, --markdown and --bench can't be used with --sandbox".to_string());
+    }
+
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn selectors() {
+        assert_eq!(Selector::Last, parse(&[]).unwrap().selector);
+        assert_eq!(Selector::All, parse(&["all"]).unwrap().selector);
+        assert_eq!(Selector::One(10), parse(&["10"]).unwrap().selector);
+        assert!(parse(&["0"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn visualisation() {
+        assert!(parse(&[]).unwrap().visualize.is_none());
+
+        let renderer = parse(&["--visualize"]).unwrap().visualize.unwrap();
+        assert!(!renderer.step);
+
+        let renderer = parse(&["14", "--delay", "250", "--step"]).unwrap().visualize.unwrap();
+        assert_eq!(Duration::from_millis(250), renderer.delay);
+        assert!(renderer.step);
+
+        assert!(parse(&["--delay", "soon"]).is_err());
+    }
+
+    #[test]
+    fn export() {
+        assert!(parse(&[]).unwrap().export.is_none());
+
+        let options = parse(&["10", "--export", "out"]).unwrap();
+        assert_eq!(Some(PathBuf::from("out")), options.export);
+        assert!(options.visualize.is_none());
+
+        assert!(parse(&["--export"]).is_err());
+
+        assert_eq!(Some(PathBuf::from("graphs")), parse(&["8", "--dot", "graphs"]).unwrap().dot);
+        assert!(parse(&["--dot"]).is_err());
+    }
+
+    #[test]
+    fn timeouts() {
+        assert_eq!(Some(DEFAULT_TIMEOUT), parse(&[]).unwrap().timeout);
+        assert_eq!(Some(Duration::from_secs(5)), parse(&["--timeout", "5"]).unwrap().timeout);
+        assert_eq!(None, parse(&["--timeout", "0"]).unwrap().timeout);
+        assert!(parse(&["--timeout", "-1"]).is_err());
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!(Command::Run, parse(&["all"]).unwrap().command);
+
+        let options = parse(&["readme", "--runs", "3", "--reveal"]).unwrap();
+        assert_eq!(Command::Readme, options.command);
+        assert_eq!(ReadmeOptions { runs: 3, reveal: true, from_store: false }, options.readme);
+        assert!(parse(&["readme", "--from-store"]).unwrap().readme.from_store);
+        assert_eq!(Command::Doctor, parse(&["doctor"]).unwrap().command);
+        let options = parse(&["fetch", "12"]).unwrap();
+        assert_eq!((Command::Fetch, Selector::One(12)), (options.command, options.selector));
+
+        let options = parse(&["submit", "8", "--part", "2", "--answer", "-3"]).unwrap();
+        assert_eq!(Command::Submit, options.command);
+        assert_eq!(SubmitOptions { part: Some(2), answer: Some(-3), force: false }, options.submit);
+        assert!(parse(&["submit", "--force"]).unwrap().submit.force);
+        assert!(parse(&["submit", "--part", "3"]).is_err());
+
+        let options = parse(&["extract", "day8.html", "--yes"]).unwrap();
+        assert_eq!(Command::Extract, options.command);
+        assert_eq!(ExtractOptions { html: Some(PathBuf::from("day8.html")), yes: true }, options.extract);
+        assert!(parse(&["extract"]).is_err());
+
+        assert!(!parse(&["8"]).unwrap().example);
+        let options = parse(&["8", "--example"]).unwrap();
+        assert!(options.example);
+        assert_eq!(vec!["8", "--example", "--timeout", "60"], options.child_args(8));
+        assert_eq!(vec!["8", "--timeout", "5"], parse(&["8", "--timeout", "5"]).unwrap().child_args(8));
+    }
+
+    #[test]
+    fn reports() {
+        assert!(parse(&[]).unwrap().reports.is_empty());
+        assert_eq!(2, parse(&[]).unwrap().observers().len());
+        assert_eq!(1, parse(&["--example"]).unwrap().observers().len());
+
+        let options = parse(&["all", "--bench", "--json", "out.json"]).unwrap();
+        assert_eq!(vec![Report::Benchmark, Report::Json(PathBuf::from("out.json"))], options.reports);
+        assert!(parse(&["--markdown"]).is_err());
+    }
+
+    #[test]
+    fn sandbox() {
+        assert!(parse(&[]).unwrap().sandbox.is_none());
+        assert_eq!(Some(Limits::default()), parse(&["--sandbox"]).unwrap().sandbox);
+        assert_eq!(
+            Some(Limits { memory_mib: 512, cpu_secs: 10 }),
+            parse(&["all", "--memory", "512", "--cpu", "10"]).unwrap().sandbox
+        );
+
+        let options = parse(&["all", "--sandbox", "--dot", "g", "--timeout", "0"]).unwrap();
+        assert_eq!(vec!["12", "--timeout", "0", "--dot", "g"], options.child_args(12));
+
+        assert!(parse(&["all", "--sandbox", "--visualize"]).is_err());
+        assert!(parse(&["--bench", "--cpu", "10"]).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeSet;
+use std::fs::{metadata, read_dir};
+use std::path::Path;
+use crate::answers::AnswerStore;
+use crate::examples::ExampleManifest;
+use crate::{get_repo_root, Factory};
+
+/// The result of one check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub ok: bool,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(ok: bool, message: String) -> Self {
+        Finding { ok, message }
+    }
+}
+
+/// Checks everything the registered days need from the environment, printing each finding.
+/// Returns whether everything was in order.
+pub fn run(days: &[Factory]) -> bool {
+    let root = get_repo_root();
+    let names: Vec<(usize, usize)> = days.iter().map(|factory| factory().name()).collect();
+    let findings = examine(&root, &names);
+
+    for finding in &findings {
+        println!("{} {}", if finding.ok { "ok  " } else { "FAIL" }, finding.message);
+    }
+
+    let problems = findings.iter().filter(|f| !f.ok).count();
+    println!("{problems} problem(s) found");
+    problems == 0
+}
+
+/// Checks the files under `root` for the days in `names`.
+pub fn examine(root: &Path, names: &[(usize, usize)]) -> Vec<Finding> {
+    let mut findings = vec![Finding::new(
+        root.join("aoc").is_dir(),
+        format!("Repository root resolves to {}", root.join("aoc").display()),
+    )];
+
+    let years: BTreeSet<usize> = names.iter().map(|&(year, _)| year).collect();
+    for year in years {
+        let crate_dir = root.join(format!("aoc/crates/aoc{year}"));
+        let input_dir = crate_dir.join("input");
+        let test_dir = crate_dir.join("test");
+        findings.push(Finding::new(input_dir.is_dir(), format!("Input directory is {}", input_dir.display())));
+
+        let store = AnswerStore::load(crate_dir.join("answers.txt"));
+        if let Err(error) = &store {
+            findings.push(Finding::new(false, format!("Unable to read the answers store: {error}")));
+        }
+        let manifest = ExampleManifest::load(test_dir.join("examples.txt"));
+        if let Err(error) = &manifest {
+            findings.push(Finding::new(false, format!("Unable to read the example manifest: {error}")));
+        }
+        let (store, manifest) = (store.unwrap_or_default(), manifest.unwrap_or_default());
+
+        let days: Vec<usize> = names.iter().filter(|&&(y, _)| y == year).map(|&(_, day)| day).collect();
+        for &day in &days {
+            let label = format!("{year} day {day}:");
+
+            let input = input_dir.join(format!("{year}-{day:02}.txt"));
+            let size = metadata(&input).map(|m| m.len());
+            findings.push(match size {
+                Ok(0) => Finding::new(false, format!("{label} input file {} is empty", input.display())),
+                Ok(_) => Finding::new(true, format!("{label} input file present")),
+                Err(_) => Finding::new(false, format!("{label} input file {} is missing", input.display())),
+            });
+
+            for part in 1..=2 {
+                findings.push(match manifest.get(day, part) {
+                    Some(example) if test_dir.join(&example.file).is_file() => {
+                        Finding::new(true, format!("{label} part {part} example {} expects {}", example.file, example.expected))
+                    }
+                    Some(example) => Finding::new(
+                        false,
+                        format!("{label} part {part} example file {} is missing", test_dir.join(&example.file).display()),
+                    ),
+                    None => Finding::new(false, format!("{label} part {part} has no expected example answer")),
+                });
+
+                findings.push(match store.get(day, part) {
+                    Some(_) => Finding::new(true, format!("{label} part {part} answer recorded")),
+                    None => Finding::new(false, format!("{label} part {part} has no recorded answer")),
+                });
+            }
+        }
+
+        for dir in [&input_dir, &test_dir] {
+            for file in unregistered_files(dir, year, &days) {
+                findings.push(Finding::new(false, format!("{} has no registered day", dir.join(file).display())));
+            }
+        }
+    }
+
+    findings
+}
+
+/// Files in `dir` that don't belong to any of `days`, going by names like `2023-08b.txt`.
+fn unregistered_files(dir: &Path, year: usize, days: &[usize]) -> Vec<String> {
+    let Ok(entries) = read_dir(dir) else {
+        return vec![];
+    };
+
+    let prefix = format!("{year}-");
+    let mut files: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name != "examples.txt")
+        .filter(|name| {
+            let day = name
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.get(..2))
+                .and_then(|digits| digits.parse::<usize>().ok());
+            !day.is_some_and(|day| days.contains(&day))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, write};
+    use super::*;
+    use crate::testing::TempPath;
+
+    #[test]
+    fn finds_problems() {
+        let root = TempPath::new("doctor");
+        let crate_dir = root.join("aoc/crates/aoc2023");
+        create_dir_all(crate_dir.join("input")).unwrap();
+        create_dir_all(crate_dir.join("test")).unwrap();
+
+        write(crate_dir.join("input/2023-01.txt"), "1abc2\n").unwrap();
+        write(crate_dir.join("input/2023-02.txt"), "").unwrap();
+        write(crate_dir.join("input/2023-09.txt"), "0 3 6\n").unwrap();
+        write(crate_dir.join("test/2023-01a.txt"), "1abc2\n").unwrap();
+        write(crate_dir.join("test/notes.md"), "").unwrap();
+        write(crate_dir.join("test/examples.txt"), "1 1 2023-01a.txt 142\n1 2 2023-01b.txt 281\n").unwrap();
+        write(crate_dir.join("answers.txt"), "1 1 54951\n").unwrap();
+
+        let findings = examine(&root, &[(2023, 1), (2023, 2)]);
+        let failed: Vec<&str> = findings.iter().filter(|f| !f.ok).map(|f| f.message.as_str()).collect();
+
+        assert!(findings[0].ok);
+        assert!(failed.iter().any(|m| m.starts_with("2023 day 1: part 2 example file") && m.ends_with("2023-01b.txt is missing")));
+        assert!(failed.contains(&"2023 day 1: part 2 has no recorded answer"));
+        assert!(failed.iter().any(|m| m.starts_with("2023 day 2: input file") && m.ends_with("is empty")));
+        assert!(failed.contains(&"2023 day 2: part 1 has no expected example answer"));
+        assert!(failed.iter().any(|m| m.ends_with("2023-09.txt has no registered day")));
+        assert!(failed.iter().any(|m| m.ends_with("notes.md has no registered day")));
+        assert_eq!(9, failed.len());
+    }
+}
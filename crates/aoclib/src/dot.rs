@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::write;
+use std::path::Path;
+use crate::intern::Graph;
+use crate::visualize::Colour;
+
+/// A directed graph in Graphviz DOT form. Nodes and edges are written in the order they were added.
+#[derive(Debug, Default, Clone)]
+pub struct Dot {
+    name: String,
+    nodes: Vec<(String, Option<Colour>)>,
+    index: HashMap<String, usize>,
+    edges: Vec<(String, String, Option<String>)>,
+}
+
+impl Dot {
+    pub fn new(name: &str) -> Self {
+        Dot { name: name.to_string(), ..Dot::default() }
+    }
+
+    /// Every node and edge of `graph`, with edges labelled by their value.
+    pub fn from_graph<E: Display>(name: &str, graph: &Graph<E>) -> Self {
+        let mut dot = Dot::new(name);
+        graph.nodes().for_each(|(_, label)| dot.add_node(label));
+        for (id, label) in graph.nodes() {
+            for (to, edge) in graph.edges(id) {
+                dot.add_edge(label, graph.label(*to), Some(&edge.to_string()));
+            }
+        }
+        dot
+    }
+
+    pub fn add_node(&mut self, node: &str) {
+        self.node_index(node);
+    }
+
+    pub fn add_edge(&mut self, from: &str, to: &str, label: Option<&str>) -> &mut Self {
+        self.add_node(from);
+        self.add_node(to);
+        self.edges.push((from.to_string(), to.to_string(), label.map(str::to_string)));
+        self
+    }
+
+    /// Fills `nodes` with `colour`, adding any that aren't in the graph yet.
+    pub fn highlight<'a, I: IntoIterator<Item = &'a str>>(&mut self, nodes: I, colour: Colour) -> &mut Self {
+        for node in nodes {
+            let i = self.node_index(node);
+            self.nodes[i].1 = Some(colour);
+        }
+        self
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        write(path, self.to_string())
+    }
+
+    fn node_index(&mut self, node: &str) -> usize {
+        if let Some(&i) = self.index.get(node) {
+            return i;
+        }
+
+        self.index.insert(node.to_string(), self.nodes.len());
+        self.nodes.push((node.to_string(), None));
+        self.nodes.len() - 1
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "digraph {} {{", quote(&self.name))?;
+
+        for (node, colour) in &self.nodes {
+            match colour {
+                Some(colour) => {
+                    let [r, g, b] = colour.rgb();
+                    writeln!(f, "    {} [style=filled, fillcolor=\"#{r:02x}{g:02x}{b:02x}\"];", quote(node))?
+                }
+                None => writeln!(f, "    {};", quote(node))?,
+            }
+        }
+
+        for (from, to, label) in &self.edges {
+            match label {
+                Some(label) => writeln!(f, "    {} -> {} [label={}];", quote(from), quote(to), quote(label))?,
+                None => writeln!(f, "    {} -> {};", quote(from), quote(to))?,
+            }
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intern::GraphBuilder;
+
+    #[test]
+    fn writes_nodes_edges_and_highlights() {
+        let mut dot = Dot::new("test");
+        dot.add_edge("a", "b", Some("L")).add_edge("b", "a", None);
+        dot.highlight(["b", "c\""], Colour::Rgb(255, 0, 16));
+
+        let expected = "digraph \"test\" {\n    \"a\";\n    \"b\" [style=filled, fillcolor=\"#ff0010\"];\n    \
+            \"c\\\"\" [style=filled, fillcolor=\"#ff0010\"];\n    \"a\" -> \"b\" [label=\"L\"];\n    \"b\" -> \"a\";\n}\n";
+        assert_eq!(expected, dot.to_string());
+    }
+
+    #[test]
+    fn converts_graphs() {
+        let mut builder = GraphBuilder::new();
+        builder.add_edge("AAA", "BBB", 'L').add_edge("AAA", "CCC", 'R');
+        let dot = Dot::from_graph("g", &builder.build());
+
+        let text = dot.to_string();
+        assert!(text.contains("\"AAA\" -> \"BBB\" [label=\"L\"];\n    \"AAA\" -> \"CCC\" [label=\"R\"];"));
+        assert_eq!(3, text.lines().filter(|line| !line.contains("->") && line.starts_with("    ")).count());
+    }
+}
@@ -0,0 +1,85 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::{get_repo_root, records};
+
+/// One puzzle example: the file under the test directory and the answer it should give.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: usize,
+    pub part: usize,
+    pub file: String,
+    pub expected: i64,
+}
+
+/// The examples for one year, kept in `crates/aoc<year>/test/examples.txt` as lines of
+/// `<day> <part> <file> <expected>`.
+#[derive(Debug, Default, Clone)]
+pub struct ExampleManifest {
+    path: PathBuf,
+    examples: Vec<Example>,
+}
+
+pub fn test_dir(year: usize) -> PathBuf {
+    get_repo_root().join(format!("aoc/crates/aoc{year}/test"))
+}
+
+pub fn manifest_path(year: usize) -> PathBuf {
+    test_dir(year).join("examples.txt")
+}
+
+impl ExampleManifest {
+    /// Loads the manifest at `path`, which is empty if the file doesn't exist yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let examples = records::load(&path, "<day> <part> <file> <expected>", |fields| match *fields {
+            [day, part, file, expected] => Some(Example {
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                file: file.to_string(),
+                expected: expected.parse().ok()?,
+            }),
+            _ => None,
+        })?;
+
+        Ok(ExampleManifest { path, examples })
+    }
+
+    pub fn examples(&self) -> &[Example] {
+        &self.examples
+    }
+
+    /// The example for one part of a day.
+    pub fn get(&self, day: usize, part: usize) -> Option<&Example> {
+        self.examples.iter().find(|e| e.day == day && e.part == part)
+    }
+
+    /// Adds an example, replacing any already recorded for that part.
+    pub fn record(&mut self, example: Example) {
+        self.examples.retain(|e| (e.day, e.part) != (example.day, example.part));
+        self.examples.push(example);
+        self.examples.sort_by_key(|e| (e.day, e.part));
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let lines = self.examples.iter().map(|e| format!("{} {} {} {}", e.day, e.part, e.file, e.expected));
+        records::save(&self.path, "day part file expected", lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempPath;
+
+    #[test]
+    fn loads_fields() {
+        let path = TempPath::with_contents("examples.txt", "10 2 2023-10b.txt 10\n1 1 2023-01a.txt -142\n");
+        let manifest = ExampleManifest::load(&path).unwrap();
+
+        assert_eq!(
+            Some(&Example { day: 1, part: 1, file: "2023-01a.txt".to_string(), expected: -142 }),
+            manifest.get(1, 1)
+        );
+        assert_eq!(None, manifest.get(10, 1));
+    }
+}
@@ -0,0 +1,263 @@
+use std::fs::{create_dir_all, read_to_string, write};
+use std::io::{self, BufRead, Write};
+use crate::cli::Options;
+use crate::examples::{manifest_path, test_dir, Example, ExampleManifest};
+use crate::html::text_of;
+use crate::{Factory, Selector};
+
+/// The examples in one part of a puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartExamples {
+    /// The text of each `<pre><code>` block, in order.
+    pub blocks: Vec<String>,
+    /// The last emphasised number, which is the answer for the last block.
+    pub answer: Option<i64>,
+}
+
+/// What a saved puzzle description holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extracted {
+    /// The day from the `--- Day N: ... ---` heading.
+    pub day: Option<usize>,
+    /// One entry per part the page describes.
+    pub parts: Vec<PartExamples>,
+}
+
+/// Pulls the examples out of a puzzle description saved from the site.
+pub fn extract(html: &str) -> Extracted {
+    let day = between(html, "--- Day ", ":").first().and_then(|day| day.trim().parse().ok());
+
+    let parts = between(html, "<article", "</article>")
+        .into_iter()
+        .map(|article| {
+            let blocks = between(article, "<pre><code>", "</code></pre>").into_iter().map(text_of).collect();
+            let answer = between(article, "<em>", "</em>").into_iter().rev().find_map(|em| text_of(em).trim().parse().ok());
+            PartExamples { blocks, answer }
+        })
+        .collect();
+
+    Extracted { day, parts }
+}
+
+/// Every piece of `text` between `start` and the next `end`.
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut result = vec![];
+    let mut rest = text;
+    while let Some(from) = rest.find(start) {
+        rest = &rest[from + start.len()..];
+        let Some(to) = rest.find(end) else {
+            break;
+        };
+        result.push(&rest[..to]);
+        rest = &rest[to + end.len()..];
+    }
+    result
+}
+
+/// The example files and manifest entries for `day` of `year`. Each part uses its last block,
+/// or part 1's if it has none of its own. A part keeps the file `manifest` already names for it,
+/// unless that would put two different blocks in one file. Otherwise parts are named
+/// `<year>-<day>.txt` if they share a block and `<year>-<day>a.txt` and `<year>-<day>b.txt` if
+/// they don't.
+pub fn plan(year: usize, day: usize, extracted: &Extracted, manifest: &ExampleManifest) -> Vec<(Example, String)> {
+    let mut chosen: Vec<(usize, &String, i64)> = vec![];
+    for (i, part) in extracted.parts.iter().take(2).enumerate() {
+        let block = part.blocks.last().or_else(|| chosen.first().map(|&(_, block, _)| block));
+        if let (Some(block), Some(answer)) = (block, part.answer) {
+            chosen.push((i + 1, block, answer));
+        }
+    }
+
+    let shared = chosen.windows(2).all(|pair| pair[0].1 == pair[1].1);
+    let default_file = |part| {
+        let suffix = if shared { "" } else if part == 1 { "a" } else { "b" };
+        format!("{year}-{day:02}{suffix}.txt")
+    };
+    let mut files: Vec<String> = chosen
+        .iter()
+        .map(|&(part, _, _)| manifest.get(day, part).map_or_else(|| default_file(part), |e| e.file.clone()))
+        .collect();
+    if !shared && files.windows(2).any(|pair| pair[0] == pair[1]) {
+        files = chosen.iter().map(|&(part, _, _)| default_file(part)).collect();
+    }
+
+    chosen
+        .into_iter()
+        .zip(files)
+        .map(|((part, block, expected), file)| {
+            let mut contents = block.clone();
+            if !contents.ends_with('\n') {
+                contents.push('\n');
+            }
+            (Example { day, part, file, expected }, contents)
+        })
+        .collect()
+}
+
+/// Extracts the examples from `options.extract.html`, shows them, and once confirmed writes them
+/// to the test directory and the example manifest. Returns false if anything went wrong.
+pub fn run(days: &[Factory], options: &Options) -> bool {
+    let Some(path) = &options.extract.html else {
+        eprintln!("Extract needs a saved puzzle description");
+        return false;
+    };
+    let html = match read_to_string(path) {
+        Ok(html) => html,
+        Err(error) => {
+            eprintln!("Unable to read {}: {error}", path.display());
+            return false;
+        }
+    };
+
+    let extracted = extract(&html);
+    let day = match (extracted.day, options.selector) {
+        (Some(day), _) | (None, Selector::One(day)) => day,
+        _ => {
+            eprintln!("{} doesn't say which day it is, give the day as well", path.display());
+            return false;
+        }
+    };
+    let Some(year) = days.first().map(|factory| factory().name().0) else {
+        return false;
+    };
+
+    let manifest = match ExampleManifest::load(manifest_path(year)) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            eprintln!("Unable to read the example manifest: {error}");
+            return false;
+        }
+    };
+
+    let planned = plan(year, day, &extracted, &manifest);
+    if planned.is_empty() {
+        eprintln!("No examples with answers found in {}", path.display());
+        return false;
+    }
+
+    let dir = test_dir(year);
+    for (example, contents) in &planned {
+        let replaces = if dir.join(&example.file).exists() { ", replacing the existing file" } else { "" };
+        println!("Day {day} part {}: {} expects {}{replaces}", example.part, example.file, example.expected);
+        for line in contents.lines() {
+            println!("    {line}");
+        }
+    }
+
+    if !options.extract.yes && !confirm("Write these examples?") {
+        println!("Nothing written");
+        return true;
+    }
+
+    match save(year, manifest, &planned) {
+        Ok(()) => {
+            println!("Updated {}", manifest_path(year).display());
+            true
+        }
+        Err(error) => {
+            eprintln!("Unable to write the examples: {error}");
+            false
+        }
+    }
+}
+
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).is_ok() && matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+fn save(year: usize, mut manifest: ExampleManifest, planned: &[(Example, String)]) -> io::Result<()> {
+    let dir = test_dir(year);
+    create_dir_all(&dir)?;
+
+    for (example, contents) in planned {
+        write(dir.join(&example.file), contents)?;
+        manifest.record(example.clone());
+    }
+    manifest.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_8: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 8: Haunted Wasteland ---</h2><p>For example:</p>
+<pre><code>RL
+
+AAA = (BBB, CCC)
+ZZZ = (ZZZ, ZZZ)
+</code></pre>
+<p>Starting with <code>AAA</code>, you need to look up the next element. Here, <code><em>2</em></code> steps are required.</p>
+<pre><code>LLR
+
+AAA = (BBB, BBB)
+ZZZ = (ZZZ, ZZZ)
+</code></pre>
+<p>This takes <code><em>6</em></code> steps.</p>
+</article>
+<p>Your puzzle answer was <code>12169</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>LR
+
+11A = (11B, XXX)
+XXX = (XXX, XXX)
+</code></pre>
+<p>So, in this example, you end up entirely on nodes that end in <code>Z</code> after <code><em>6</em></code> steps.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn extracts_examples() {
+        let extracted = extract(DAY_8);
+        assert_eq!(Some(8), extracted.day);
+        assert_eq!(2, extracted.parts.len());
+        assert_eq!(2, extracted.parts[0].blocks.len());
+        assert!(extracted.parts[0].blocks[1].starts_with("LLR\n\nAAA = (BBB, BBB)\n"));
+        assert_eq!(Some(6), extracted.parts[0].answer);
+        assert_eq!(Some(6), extracted.parts[1].answer);
+
+        let planned = plan(2023, 8, &extracted, &ExampleManifest::default());
+        let files: Vec<(&str, i64)> = planned.iter().map(|(e, _)| (e.file.as_str(), e.expected)).collect();
+        assert_eq!(vec![("2023-08a.txt", 6), ("2023-08b.txt", 6)], files);
+        assert_eq!("LR\n\n11A = (11B, XXX)\nXXX = (XXX, XXX)\n", planned[1].1);
+    }
+
+    #[test]
+    fn shares_a_block() {
+        let html = "<article><h2>--- Day 9: Mirage Maintenance ---</h2><pre><code>0 3 &lt; 6\n</code></pre>\
+            <p>The sum is <em><code>114</code></em>.</p></article>\
+            <article><p>Adding them up gives <code><em>2</em></code>.</p></article>";
+
+        let planned = plan(2023, 9, &extract(html), &ExampleManifest::default());
+        assert_eq!(2, planned.len());
+        assert_eq!(Example { day: 9, part: 1, file: "2023-09.txt".to_string(), expected: 114 }, planned[0].0);
+        assert_eq!(Example { day: 9, part: 2, file: "2023-09.txt".to_string(), expected: 2 }, planned[1].0);
+        assert_eq!("0 3 < 6\n", planned[1].1);
+
+        assert!(plan(2023, 9, &extract("<article><p>No examples</p></article>"), &ExampleManifest::default()).is_empty());
+    }
+
+    #[test]
+    fn keeps_manifest_names() {
+        let example = |part, file: &str| Example { day: 10, part, file: file.to_string(), expected: 4 };
+        let html = "<article><pre><code>.S-7.\n</code></pre><p><code><em>4</em></code></p></article>\
+            <article><pre><code>..S..\n</code></pre><p><code><em>4</em></code></p></article>";
+        let files = |manifest: &ExampleManifest| -> Vec<String> {
+            plan(2023, 10, &extract(html), manifest).into_iter().map(|(e, _)| e.file).collect()
+        };
+
+        let mut manifest = ExampleManifest::default();
+        manifest.record(example(1, "2023-10.txt"));
+        manifest.record(example(2, "2023-10b.txt"));
+        assert_eq!(vec!["2023-10.txt", "2023-10b.txt"], files(&manifest));
+
+        // The parts no longer share an example, so they can't keep sharing a file
+        manifest.record(example(1, "2023-10x.txt"));
+        manifest.record(example(2, "2023-10x.txt"));
+        assert_eq!(vec!["2023-10a.txt", "2023-10b.txt"], files(&manifest));
+    }
+}
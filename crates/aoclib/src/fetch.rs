@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::{create_dir_all, metadata, read_to_string, write};
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::{get_repo_root, input_file};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum FetchError {
+    NoSession,
+    Locked { year: usize, day: usize },
+    Status(u16, String),
+    Transport(String),
+    Io(io::Error),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::NoSession => write!(f, "no session token, set AOC_SESSION or `session` in {}", config_path().display()),
+            FetchError::Locked { year, day } => write!(f, "{year} day {day} isn't unlocked yet"),
+            FetchError::Status(status, body) => write!(f, "the server answered {status}: {}", body.trim()),
+            FetchError::Transport(message) => write!(f, "{message}"),
+            FetchError::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(error: io::Error) -> Self {
+        FetchError::Io(error)
+    }
+}
+
+impl From<ureq::Error> for FetchError {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(status, response) => FetchError::Status(status, response.into_string().unwrap_or_default()),
+            ureq::Error::Transport(transport) => FetchError::Transport(transport.to_string()),
+        }
+    }
+}
+
+/// Settings for talking to the puzzle site. Each comes from an `AOC_*` environment variable, then
+/// the config file, then a default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    /// How the site can reach whoever runs this, sent in the User-Agent.
+    pub contact: Option<String>,
+}
+
+/// `$AOC_CONFIG`, or `aoc/config` under the user's config directory. The file holds
+/// `key = value` lines for `session`, `base_url` and `contact`.
+pub fn config_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("AOC_CONFIG") {
+        return PathBuf::from(path);
+    }
+
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_default();
+    config_dir.join("aoc/config")
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let file = read_to_string(config_path()).unwrap_or_default();
+        Config::from_sources(&file, |key| std::env::var(key).ok())
+    }
+
+    fn from_sources<F: Fn(&str) -> Option<String>>(file: &str, env: F) -> Self {
+        let values: HashMap<&str, &str> = file
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim().trim_matches('"')))
+            .collect();
+        let get = |env_key: &str, key: &str| env(env_key).or_else(|| values.get(key).map(|v| v.to_string())).filter(|v| !v.is_empty());
+
+        Config {
+            session: get("AOC_SESSION", "session"),
+            base_url: get("AOC_BASE_URL", "base_url").unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            contact: get("AOC_CONTACT", "contact"),
+        }
+    }
+
+    pub fn user_agent(&self) -> String {
+        let agent = format!("aoclib/{} (Rust puzzle runner", env!("CARGO_PKG_VERSION"));
+        match &self.contact {
+            Some(contact) => format!("{agent}; {contact})"),
+            None => format!("{agent})"),
+        }
+    }
+}
+
+/// A client for the puzzle site.
+pub struct Client {
+    config: Config,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&config.user_agent())
+            .timeout(Duration::from_secs(30))
+            .build();
+        Client { config, agent }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn session(&self) -> Result<&str, FetchError> {
+        self.config.session.as_deref().ok_or(FetchError::NoSession)
+    }
+
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.config.base_url.trim_end_matches('/'))
+    }
+
+    /// Refuses puzzles that haven't unlocked yet, so we never ask the site for them.
+    pub(crate) fn check_unlocked(&self, year: usize, day: usize) -> Result<(), FetchError> {
+        if is_unlocked(year, day, SystemTime::now()) {
+            Ok(())
+        } else {
+            Err(FetchError::Locked { year, day })
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, FetchError> {
+        let session = self.session()?;
+        let response = self.agent.get(&self.url(path)).set("Cookie", &format!("session={session}")).call()?;
+        Ok(response.into_string()?)
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, FetchError> {
+        let session = self.session()?;
+        let response = self.agent.post(&self.url(path)).set("Cookie", &format!("session={session}")).send_form(form)?;
+        Ok(response.into_string()?)
+    }
+
+    pub fn fetch_input(&self, year: usize, day: usize) -> Result<String, FetchError> {
+        self.check_unlocked(year, day)?;
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+
+    /// Downloads a day's input to where the runner looks for it, unless it's already there.
+    /// Returns the path and whether it had to be downloaded.
+    pub fn ensure_input(&self, year: usize, day: usize) -> Result<(PathBuf, bool), FetchError> {
+        let path = get_repo_root().join(input_file(year, day));
+        if metadata(&path).is_ok_and(|m| m.len() > 0) {
+            return Ok((path, false));
+        }
+
+        let input = self.fetch_input(year, day)?;
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir)?;
+        }
+        write(&path, input)?;
+        Ok((path, true))
+    }
+}
+
+/// Puzzles unlock at midnight US Eastern time (05:00 UTC) on the first 25 days of December.
+pub fn is_unlocked(year: usize, day: usize, now: SystemTime) -> bool {
+    if !(1..=25).contains(&day) {
+        return false;
+    }
+
+    let unlock_secs = days_from_civil(year as i64, 12, day as i64) * 86_400 + 5 * 3_600;
+    let now_secs = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+    now_secs >= unlock_secs
+}
+
+/// Days from 1970-01-01 to the given date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Fetches the input for each of `days` of `year`, skipping any already cached.
+pub fn run(year: usize, days: &[usize]) -> bool {
+    let client = Client::new(Config::load());
+    let mut ok = true;
+
+    for &day in days {
+        match client.ensure_input(year, day) {
+            Ok((path, true)) => println!("Downloaded {}", path.display()),
+            Ok((path, false)) => println!("Already have {}", path.display()),
+            Err(error) => {
+                eprintln!("Unable to fetch {year} day {day}: {error}");
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
+
+/// A stand-in for the puzzle site that answers one request, for tests.
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serves `status` and `body` to the first connection. The handle yields the request it got.
+    pub fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!("HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(base_url: &str) -> Config {
+        Config { session: Some("abc123".to_string()), base_url: base_url.to_string(), contact: Some("me@example.com".to_string()) }
+    }
+
+    #[test]
+    fn reads_config() {
+        let file = "# settings\nsession = \"from-file\"\ncontact = me@example.com\n";
+
+        let config = Config::from_sources(file, |_| None);
+        assert_eq!(Some("from-file".to_string()), config.session);
+        assert_eq!(DEFAULT_BASE_URL, config.base_url);
+
+        let config = Config::from_sources(file, |key| (key == "AOC_SESSION").then(|| "from-env".to_string()));
+        assert_eq!(Some("from-env".to_string()), config.session);
+        assert_eq!("aoclib/0.1.0 (Rust puzzle runner; me@example.com)", config.user_agent());
+    }
+
+    #[test]
+    fn unlock_times() {
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        // 2023-12-01T05:00:00Z
+        let unlock = 1_701_406_800;
+
+        assert_eq!(unlock as i64 / 86_400, days_from_civil(2023, 12, 1));
+        assert!(!is_unlocked(2023, 1, at(unlock - 1)));
+        assert!(is_unlocked(2023, 1, at(unlock)));
+        assert!(!is_unlocked(2023, 2, at(unlock)));
+        assert!(!is_unlocked(2023, 26, at(unlock * 2)));
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (url, request) = mock::serve_once("200 OK", "1abc2\n");
+        let client = Client::new(config(&format!("{url}/")));
+
+        assert_eq!("1abc2\n", client.fetch_input(2023, 1).unwrap());
+        let request = request.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc123\r\n"));
+        assert!(request.contains("User-Agent: aoclib/0.1.0 (Rust puzzle runner; me@example.com)\r\n"));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let (url, request) = mock::serve_once("400 Bad Request", "Please log in");
+        let client = Client::new(config(&url));
+
+        let error = client.fetch_input(2023, 2).unwrap_err();
+        request.join().unwrap();
+        assert!(matches!(&error, FetchError::Status(400, body) if body == "Please log in"));
+
+        assert!(matches!(client.fetch_input(2999, 1), Err(FetchError::Locked { year: 2999, day: 1 })));
+        let anonymous = Client::new(Config { session: None, ..config(&url) });
+        assert!(matches!(anonymous.fetch_input(2023, 1), Err(FetchError::NoSession)));
+    }
+}
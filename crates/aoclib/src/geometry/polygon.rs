@@ -0,0 +1,73 @@
+use crate::math::gcd;
+
+/// Twice the signed area of a simple polygon, positive when the vertices run anticlockwise.
+///
+/// Uses the shoelace formula, so the result is exact for integer vertices. The polygon is closed
+/// implicitly: the last vertex connects back to the first.
+pub fn signed_double_area(vertices: &[(i64, i64)]) -> i64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum()
+}
+
+/// Twice the area of a simple polygon. Doubling keeps half-unit areas exact.
+pub fn double_area(vertices: &[(i64, i64)]) -> i64 {
+    signed_double_area(vertices).abs()
+}
+
+pub fn area(vertices: &[(i64, i64)]) -> f64 {
+    double_area(vertices) as f64 / 2.0
+}
+
+/// Number of lattice points that lie on the polygon's edges.
+pub fn boundary_points(vertices: &[(i64, i64)]) -> i64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| gcd((x2 - x1).abs(), (y2 - y1).abs()))
+        .sum()
+}
+
+/// Number of lattice points strictly inside the polygon, from Pick's theorem `A = I + B/2 - 1`.
+pub fn interior_points(vertices: &[(i64, i64)]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+fn edges(vertices: &[(i64, i64)]) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+
+        assert_eq!(32, signed_double_area(&square));
+        assert_eq!(16.0, area(&square));
+        assert_eq!(16, boundary_points(&square));
+        assert_eq!(9, interior_points(&square));
+    }
+
+    #[test]
+    fn clockwise_triangle() {
+        let triangle = [(0, 0), (0, 3), (3, 0)];
+
+        assert_eq!(-9, signed_double_area(&triangle));
+        assert_eq!(4.5, area(&triangle));
+        assert_eq!(9, boundary_points(&triangle));
+        assert_eq!(1, interior_points(&triangle));
+    }
+
+    #[test]
+    fn collinear_vertices() {
+        let square = [(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (1, 2), (0, 2), (0, 1)];
+
+        assert_eq!(8, double_area(&square));
+        assert_eq!(8, boundary_points(&square));
+        assert_eq!(1, interior_points(&square));
+    }
+}
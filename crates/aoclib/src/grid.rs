@@ -0,0 +1,270 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+
+pub use aoclib_derive::GridCell;
+
+/// A type that each character of a grid puzzle's input parses into, usually a fieldless enum
+/// with `#[derive(GridCell)]`.
+pub trait GridCell: Sized + 'static {
+    /// Every cell type, in declaration order.
+    const ALL: &'static [Self];
+
+    fn from_char(c: char) -> Option<Self>;
+
+    /// The character this cell is parsed from.
+    fn to_char(&self) -> char;
+
+    /// The character used to draw this cell, which defaults to the one it's parsed from.
+    fn display_char(&self) -> char {
+        self.to_char()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownCell(pub char);
+
+impl Display for UnknownCell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown grid cell {:?}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownCell {}
+
+/// Parses each non-empty line of `text` into a row of cells.
+pub fn parse_grid<T: GridCell>(text: &str) -> Result<Vec<Vec<T>>, UnknownCell> {
+    crate::input::lines(text)
+        .map(|line| {
+            line.chars()
+                .map(|c| T::from_char(c).ok_or(UnknownCell(c)))
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Connectivity::Eight => &[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)],
+        }
+    }
+}
+
+/// The in-bounds neighbours of `pos` as `(row, col)` positions.
+pub fn neighbours<T>(grid: &[Vec<T>], pos: (usize, usize), connectivity: Connectivity) -> impl Iterator<Item = (usize, usize)> + '_ {
+    connectivity
+        .offsets()
+        .iter()
+        .filter_map(move |&(dr, dc)| {
+            let row = pos.0.checked_add_signed(dr)?;
+            let col = pos.1.checked_add_signed(dc)?;
+            grid.get(row)?.get(col).map(|_| (row, col))
+        })
+}
+
+fn is_border<T>(grid: &[Vec<T>], pos: (usize, usize)) -> bool {
+    pos.0 == 0 || pos.1 == 0 || pos.0 + 1 == grid.len() || pos.1 + 1 == grid[pos.0].len()
+}
+
+/// Every position reachable from `start` through passable cells, including `start` itself.
+/// Returns an empty set if `start` isn't passable.
+pub fn flood_fill<T, F>(grid: &[Vec<T>], start: (usize, usize), connectivity: Connectivity, passable: F) -> HashSet<(usize, usize)>
+where
+    F: Fn(&T) -> bool,
+{
+    let mut filled = HashSet::new();
+
+    if !grid.get(start.0).and_then(|row| row.get(start.1)).is_some_and(&passable) {
+        return filled;
+    }
+
+    let mut queue = VecDeque::from([start]);
+    filled.insert(start);
+
+    while let Some(pos) = queue.pop_front() {
+        for next in neighbours(grid, pos, connectivity) {
+            if passable(&grid[next.0][next.1]) && filled.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    filled
+}
+
+/// The connected regions of passable cells in a grid.
+#[derive(Debug, Default)]
+pub struct Regions {
+    /// Region id of every cell, or `None` for cells that aren't passable.
+    pub labels: Vec<Vec<Option<usize>>>,
+    /// Number of cells in each region, indexed by region id.
+    pub sizes: Vec<usize>,
+    /// Ids of the regions that contain at least one cell on the edge of the grid.
+    pub touching_border: HashSet<usize>,
+}
+
+impl Regions {
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn region_of(&self, pos: (usize, usize)) -> Option<usize> {
+        self.labels.get(pos.0)?.get(pos.1).copied().flatten()
+    }
+
+    /// Ids of the regions that don't reach the edge of the grid.
+    pub fn enclosed(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.count()).filter(|id| !self.touching_border.contains(id))
+    }
+}
+
+/// Labels every connected region of passable cells. Ids are assigned in row-major order of each
+/// region's first cell.
+pub fn label_regions<T, F>(grid: &[Vec<T>], connectivity: Connectivity, passable: F) -> Regions
+where
+    F: Fn(&T) -> bool,
+{
+    let mut regions = Regions {
+        labels: grid.iter().map(|row| vec![None; row.len()]).collect(),
+        ..Regions::default()
+    };
+
+    for row in 0..grid.len() {
+        for col in 0..grid[row].len() {
+            if regions.labels[row][col].is_some() || !passable(&grid[row][col]) {
+                continue;
+            }
+
+            let id = regions.sizes.len();
+            let mut size = 0;
+            let mut queue = VecDeque::from([(row, col)]);
+            regions.labels[row][col] = Some(id);
+
+            while let Some(pos) = queue.pop_front() {
+                size += 1;
+                if is_border(grid, pos) {
+                    regions.touching_border.insert(id);
+                }
+
+                for next in neighbours(grid, pos, connectivity) {
+                    if regions.labels[next.0][next.1].is_none() && passable(&grid[next.0][next.1]) {
+                        regions.labels[next.0][next.1] = Some(id);
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            regions.sizes.push(size);
+        }
+    }
+
+    regions
+}
+
+/// Breadth first distances from the nearest of `sources`, or `None` for unreachable cells.
+/// Sources that aren't passable are ignored.
+pub fn distances<T, F, I>(grid: &[Vec<T>], sources: I, connectivity: Connectivity, passable: F) -> Vec<Vec<Option<usize>>>
+where
+    F: Fn(&T) -> bool,
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let mut distances: Vec<Vec<Option<usize>>> = grid.iter().map(|row| vec![None; row.len()]).collect();
+    let mut queue = VecDeque::new();
+
+    for source in sources {
+        if grid.get(source.0).and_then(|row| row.get(source.1)).is_some_and(&passable) && distances[source.0][source.1].is_none() {
+            distances[source.0][source.1] = Some(0);
+            queue.push_back(source);
+        }
+    }
+
+    while let Some(pos) = queue.pop_front() {
+        let distance = distances[pos.0][pos.1].unwrap() + 1;
+
+        for next in neighbours(grid, pos, connectivity) {
+            if distances[next.0][next.1].is_none() && passable(&grid[next.0][next.1]) {
+                distances[next.0][next.1] = Some(distance);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, GridCell)]
+    enum Tile {
+        #[cell('.')]
+        Open,
+        #[cell('#', display = '█')]
+        Wall,
+    }
+
+    #[test]
+    fn derived_cells() {
+        assert_eq!(&[Tile::Open, Tile::Wall], Tile::ALL);
+        assert_eq!(Ok(Tile::Wall), Tile::try_from('#'));
+        assert_eq!(Err(UnknownCell('x')), Tile::try_from('x'));
+        assert_eq!('#', Tile::Wall.to_char());
+        assert_eq!("█.", format!("{}{}", Tile::Wall, Tile::Open));
+
+        assert_eq!(Ok(vec![vec![Tile::Open, Tile::Wall], vec![Tile::Wall, Tile::Open]]), parse_grid(".#\n#.\n"));
+        assert_eq!(Err(UnknownCell('x')), parse_grid::<Tile>(".#\nx.\n"));
+    }
+
+    fn grid(s: &str) -> Vec<Vec<char>> {
+        s.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    #[test]
+    fn fill() {
+        let map = grid("..#..\n.#.#.\n..#..");
+
+        let filled = flood_fill(&map, (0, 0), Connectivity::Four, |&c| c == '.');
+        assert_eq!(5, filled.len());
+        assert!(!filled.contains(&(1, 2)));
+
+        let filled = flood_fill(&map, (0, 0), Connectivity::Eight, |&c| c == '.');
+        assert_eq!(11, filled.len());
+
+        assert!(flood_fill(&map, (0, 2), Connectivity::Four, |&c| c == '.').is_empty());
+    }
+
+    #[test]
+    fn regions() {
+        let map = grid("#####\n#..##\n#####\n.#..#");
+
+        let regions = label_regions(&map, Connectivity::Four, |&c| c == '.');
+        assert_eq!(3, regions.count());
+        assert_eq!(vec![2, 1, 2], regions.sizes);
+        assert_eq!(Some(0), regions.region_of((1, 2)));
+        assert_eq!(None, regions.region_of((0, 0)));
+        assert_eq!(vec![0], regions.enclosed().collect::<Vec<_>>());
+
+        let islands = label_regions(&map, Connectivity::Eight, |&c| c == '#');
+        assert_eq!(1, islands.count());
+    }
+
+    #[test]
+    fn multi_source_distances() {
+        let map = grid(".....\n.###.\n.....");
+
+        let distances = distances(&map, [(0, 0), (2, 4)], Connectivity::Four, |&c| c == '.');
+        assert_eq!(Some(0), distances[0][0]);
+        assert_eq!(Some(2), distances[0][2]);
+        assert_eq!(Some(1), distances[1][4]);
+        assert_eq!(Some(3), distances[2][1]);
+        assert_eq!(None, distances[1][2]);
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::{get_repo_root, records};
+use crate::report::{Name, Observer};
+use crate::submit::Verdict;
+use crate::worker::{Outcome, Phase, PhaseReport};
+
+/// What the site said about one guess. Only verdicts that say something about the answer are
+/// kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Judgement {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Judgement {
+    pub fn from_verdict(verdict: &Verdict) -> Option<Self> {
+        match verdict {
+            Verdict::Correct => Some(Judgement::Correct),
+            Verdict::TooHigh => Some(Judgement::TooHigh),
+            Verdict::TooLow => Some(Judgement::TooLow),
+            Verdict::Incorrect => Some(Judgement::Wrong),
+            _ => None,
+        }
+    }
+
+    fn word(&self) -> &'static str {
+        match self {
+            Judgement::Correct => "correct",
+            Judgement::TooHigh => "high",
+            Judgement::TooLow => "low",
+            Judgement::Wrong => "wrong",
+        }
+    }
+
+    fn from_word(word: &str) -> Option<Self> {
+        [Judgement::Correct, Judgement::TooHigh, Judgement::TooLow, Judgement::Wrong]
+            .into_iter()
+            .find(|judgement| judgement.word() == word)
+    }
+}
+
+/// Why an answer can't be right, going by earlier guesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    /// It was guessed before and was wrong.
+    Repeated(i64),
+    /// It isn't above a guess that was too low.
+    NotAbove(i64),
+    /// It isn't below a guess that was too high.
+    NotBelow(i64),
+    /// It differs from the guess that was right.
+    NotCorrect(i64),
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conflict::Repeated(guess) => write!(f, "{guess} was already guessed and was wrong"),
+            Conflict::NotAbove(low) => write!(f, "{low} was too low, so the answer is higher"),
+            Conflict::NotBelow(high) => write!(f, "{high} was too high, so the answer is lower"),
+            Conflict::NotCorrect(correct) => write!(f, "{correct} was the right answer"),
+        }
+    }
+}
+
+/// Every answer submitted for one year and what the site made of it, kept in
+/// `crates/aoc<year>/guesses.txt` as lines of `<day> <part> <answer> <correct|high|low|wrong>`.
+#[derive(Debug, Default, Clone)]
+pub struct GuessLedger {
+    path: PathBuf,
+    guesses: BTreeMap<(usize, usize), Vec<(i64, Judgement)>>,
+}
+
+pub fn ledger_path(year: usize) -> PathBuf {
+    get_repo_root().join(format!("aoc/crates/aoc{year}/guesses.txt"))
+}
+
+impl GuessLedger {
+    /// Loads the ledger at `path`, which is empty if the file doesn't exist yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let guesses = records::load(&path, "<day> <part> <answer> <correct|high|low|wrong>", |fields| match *fields {
+            [day, part, answer, judgement] => {
+                Some((day.parse().ok()?, part.parse().ok()?, answer.parse().ok()?, Judgement::from_word(judgement)?))
+            }
+            _ => None,
+        })?;
+
+        let mut ledger = GuessLedger { path, guesses: BTreeMap::new() };
+        for (day, part, answer, judgement) in guesses {
+            ledger.record(day, part, answer, judgement);
+        }
+        Ok(ledger)
+    }
+
+    /// The guesses for one part, in the order they were made.
+    pub fn guesses(&self, day: usize, part: usize) -> &[(i64, Judgement)] {
+        self.guesses.get(&(day, part)).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn record(&mut self, day: usize, part: usize, answer: i64, judgement: Judgement) {
+        self.guesses.entry((day, part)).or_default().push((answer, judgement));
+    }
+
+    /// The smallest and largest answers the guesses so far still allow, if they bound it at all, or
+    /// `None` if a guess at the edge of `i64` leaves nothing past it.
+    pub fn range(&self, day: usize, part: usize) -> Option<(Option<i64>, Option<i64>)> {
+        let (low, high) = self.bounds(day, part);
+        let low = match low {
+            Some(low) => Some(low.checked_add(1)?),
+            None => None,
+        };
+        let high = match high {
+            Some(high) => Some(high.checked_sub(1)?),
+            None => None,
+        };
+        Some((low, high))
+    }
+
+    /// Why `answer` can't be right for one part, if the guesses so far rule it out.
+    pub fn check(&self, day: usize, part: usize, answer: i64) -> Option<Conflict> {
+        let guesses = self.guesses(day, part);
+        if let Some(&(correct, _)) = guesses.iter().find(|&&(_, j)| j == Judgement::Correct) {
+            return (answer != correct).then_some(Conflict::NotCorrect(correct));
+        }
+        if guesses.iter().any(|&(guess, _)| guess == answer) {
+            return Some(Conflict::Repeated(answer));
+        }
+
+        match self.bounds(day, part) {
+            (Some(low), _) if answer <= low => Some(Conflict::NotAbove(low)),
+            (_, Some(high)) if answer >= high => Some(Conflict::NotBelow(high)),
+            _ => None,
+        }
+    }
+
+    /// The highest guess that was too low and the lowest that was too high.
+    fn bounds(&self, day: usize, part: usize) -> (Option<i64>, Option<i64>) {
+        let guesses = self.guesses(day, part);
+        let bound = |judgement| guesses.iter().filter(move |&&(_, j)| j == judgement).map(|&(answer, _)| answer);
+        (bound(Judgement::TooLow).max(), bound(Judgement::TooHigh).min())
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let lines = self.guesses.iter().flat_map(|((day, part), guesses)| {
+            guesses.iter().map(move |(answer, judgement)| format!("{day} {part} {answer} {}", judgement.word()))
+        });
+        records::save(&self.path, "day part answer verdict", lines)
+    }
+}
+
+/// Warns when a day's answer contradicts what the ledger knows about that part.
+#[derive(Debug, Default)]
+pub struct GuessChecker {
+    ledgers: BTreeMap<usize, GuessLedger>,
+}
+
+impl Observer for GuessChecker {
+    fn phase_finished(&mut self, name: Name, report: &PhaseReport) {
+        let part = match report.phase {
+            Phase::Parse => return,
+            Phase::Part1 => 1,
+            Phase::Part2 => 2,
+        };
+        let Outcome::Answer(answer) = report.outcome else {
+            return;
+        };
+
+        let ledger = self.ledgers.entry(name.0).or_insert_with(|| {
+            // Carry on with an empty ledger, which stops this being reported for every phase
+            GuessLedger::load(ledger_path(name.0)).unwrap_or_else(|error| {
+                eprintln!("Unable to read the guess ledger, answers won't be checked against it: {error}");
+                GuessLedger::default()
+            })
+        });
+        if let Some(conflict) = ledger.check(name.1, part, answer) {
+            println!("{:11} Warning: {answer} can't be right, {conflict}", "");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn judgement_words() {
+        let words = [Judgement::Correct, Judgement::TooHigh, Judgement::TooLow, Judgement::Wrong].map(|j| j.word());
+        assert_eq!(["correct", "high", "low", "wrong"], words);
+
+        for word in words {
+            assert_eq!(Some(word), Judgement::from_word(word).map(|j| j.word()));
+        }
+        assert_eq!(None, Judgement::from_word("close"));
+    }
+
+    #[test]
+    fn finds_conflicts() {
+        let mut ledger = GuessLedger::default();
+        for (answer, judgement) in [(500, Judgement::TooHigh), (100, Judgement::TooLow), (300, Judgement::TooHigh), (200, Judgement::Wrong)] {
+            ledger.record(1, 1, answer, judgement);
+        }
+
+        assert_eq!(Some((Some(101), Some(299))), ledger.range(1, 1));
+        assert_eq!(Some((None, None)), ledger.range(1, 2));
+        assert_eq!(Some(Conflict::NotAbove(100)), ledger.check(1, 1, 50));
+        assert_eq!(Some(Conflict::NotBelow(300)), ledger.check(1, 1, 400));
+        assert_eq!(Some(Conflict::Repeated(200)), ledger.check(1, 1, 200));
+        assert_eq!(None, ledger.check(1, 1, 250));
+        assert_eq!(None, ledger.check(1, 2, 200));
+
+        ledger.record(1, 1, 250, Judgement::Correct);
+        assert_eq!(None, ledger.check(1, 1, 250));
+        assert_eq!(Some(Conflict::NotCorrect(250)), ledger.check(1, 1, 251));
+    }
+
+    #[test]
+    fn guesses_at_the_edges() {
+        let mut ledger = GuessLedger::default();
+        ledger.record(1, 1, i64::MAX, Judgement::TooLow);
+        ledger.record(1, 2, i64::MIN, Judgement::TooHigh);
+
+        assert_eq!(None, ledger.range(1, 1));
+        assert_eq!(None, ledger.range(1, 2));
+        assert_eq!(Some(Conflict::NotAbove(i64::MAX)), ledger.check(1, 1, 5));
+        assert_eq!(Some(Conflict::NotBelow(i64::MIN)), ledger.check(1, 2, 5));
+    }
+}
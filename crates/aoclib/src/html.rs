@@ -0,0 +1,78 @@
+/// The text of an HTML fragment, with tags removed and entities decoded but whitespace kept.
+pub fn text_of(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+}
+
+/// Replaces the named entities the site uses and every numeric one, `&#NN;` or `&#xNN;`, with
+/// the character they stand for. Anything else after an `&` is left as it is.
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..].find(';').and_then(|end| Some((entity(&rest[1..=end])?, end + 2)));
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn entity(name: &str) -> Option<char> {
+    let (digits, radix) = match name {
+        "lt" => return Some('<'),
+        "gt" => return Some('>'),
+        "amp" => return Some('&'),
+        "quot" => return Some('"'),
+        "apos" => return Some('\''),
+        "nbsp" => return Some('\u{a0}'),
+        _ => match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+            Some(hex) => (hex, 16),
+            None => (name.strip_prefix('#')?, 10),
+        },
+    };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    char::from_u32(u32::from_str_radix(digits, radix).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_tags() {
+        assert_eq!("AAA = (BBB, CCC)\n", text_of("<code>AAA = <em>(BBB, CCC)</em>\n</code>"));
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!("<a> & \"b\" 'c'", decode_entities("&lt;a&gt; &amp; &quot;b&quot; &#39;c&apos;"));
+        assert_eq!("~ é ~", decode_entities("&#126; &#xe9; &#X7E;"));
+        assert_eq!("a & b &c; &#; &#x; &#+5; &#xg; &#1114112;", decode_entities("a & b &c; &#; &#x; &#+5; &#xg; &#1114112;"));
+        assert_eq!("&&", decode_entities("&&amp;"));
+    }
+}
@@ -0,0 +1,135 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// A puzzle input loaded into memory once. Lines, groups and fields are borrowed from it.
+///
+/// The text is normalised on the way in, so it never holds a BOM or a `\r` line ending.
+#[derive(Debug, Default, Clone)]
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    pub fn load<T: AsRef<Path>>(pathname: T) -> Self {
+        Input::from(read_to_string(pathname).expect("unable to open file"))
+    }
+
+    /// Strips trailing whitespace from every line.
+    pub fn trim_trailing_whitespace(self) -> Self {
+        Input { text: self.text.split('\n').map(str::trim_end).collect::<Vec<_>>().join("\n") }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        lines(&self.text)
+    }
+
+    pub fn byte_lines(&self) -> impl Iterator<Item = &[u8]> {
+        byte_lines(self.as_bytes())
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = &str> {
+        groups(&self.text)
+    }
+
+    pub fn fields<'a>(&'a self, separator: &'a str) -> impl Iterator<Item = &'a str> {
+        fields(&self.text, separator)
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Input::from(text.as_str())
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Input { text: normalise(text) }
+    }
+}
+
+/// Removes a leading byte order mark and converts `\r\n` and lone `\r` line endings to `\n`.
+pub fn normalise(text: &str) -> String {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// The non-empty lines of `text`.
+pub fn lines(text: &str) -> impl Iterator<Item = &str> {
+    fields(text, "\n")
+}
+
+/// The non-empty lines of `bytes`.
+pub fn byte_lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    bytes.split(|&b| b == b'\n').filter(|line| !line.is_empty())
+}
+
+/// The non-empty pieces of `text` between occurrences of `separator`.
+pub fn fields<'a>(text: &'a str, separator: &'a str) -> impl Iterator<Item = &'a str> {
+    text.split(separator).filter(|s| !s.is_empty())
+}
+
+/// The blocks of `text` separated by one or more blank lines.
+pub fn groups(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+
+    std::iter::from_fn(move || {
+        rest = rest.trim_start_matches(['\r', '\n']);
+        if rest.is_empty() {
+            return None;
+        }
+
+        let end = rest
+            .match_indices('\n')
+            .map(|(i, _)| i)
+            .find(|&i| rest[i + 1..].starts_with('\n') || rest[i + 1..].starts_with("\r\n"))
+            .unwrap_or(rest.len());
+
+        let group = rest[..end].trim_end_matches(['\r', '\n']);
+        rest = &rest[end..];
+        Some(group)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_lines_and_fields() {
+        let input = Input::from("12 34\n\n56 78\n");
+
+        assert_eq!(vec!["12 34", "56 78"], input.lines().collect::<Vec<_>>());
+        assert_eq!(vec![b"12 34".as_slice(), b"56 78"], input.byte_lines().collect::<Vec<_>>());
+        assert_eq!(vec!["12", "34\n\n56", "78\n"], input.fields(" ").collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn groups_split_on_blank_lines() {
+        let lf = Input::from("a\nb\n\nc\n\n\n\nd\n");
+        assert_eq!(vec!["a\nb", "c", "d"], lf.groups().collect::<Vec<_>>());
+
+        let crlf = Input::from("a\r\nb\r\n\r\nc\r\n");
+        assert_eq!(vec!["a\nb", "c"], crlf.groups().collect::<Vec<_>>());
+
+        assert_eq!(0, Input::default().groups().count());
+    }
+
+    #[test]
+    fn normalises_line_endings() {
+        let input = Input::from("\u{feff}a\r\nb\rc\n");
+        assert_eq!("a\nb\nc\n", input.as_str());
+
+        let padded = Input::from("a  \r\n\t\r\nb\t\n").trim_trailing_whitespace();
+        assert_eq!("a\n\nb\n", padded.as_str());
+        assert_eq!(vec!["a", "b"], padded.groups().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+/// Maps string labels to dense `u32` ids, assigned in order of first appearance.
+#[derive(Debug, Default, Clone)]
+pub struct Interner {
+    ids: HashMap<String, u32>,
+    labels: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id for `label`, assigning the next free id if it hasn't been seen before.
+    pub fn intern(&mut self, label: &str) -> u32 {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+
+        let id = u32::try_from(self.labels.len()).expect("Too many labels to intern");
+        self.ids.insert(label.to_string(), id);
+        self.labels.push(label.to_string());
+        id
+    }
+
+    pub fn get(&self, label: &str) -> Option<u32> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: u32) -> &str {
+        &self.labels[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.labels.iter().enumerate().map(|(id, label)| (id as u32, label.as_str()))
+    }
+}
+
+/// Collects labelled edges between named nodes and turns them into a [`Graph`].
+#[derive(Debug, Clone)]
+pub struct GraphBuilder<E> {
+    interner: Interner,
+    edges: Vec<(u32, u32, E)>,
+}
+
+impl<E> Default for GraphBuilder<E> {
+    fn default() -> Self {
+        GraphBuilder { interner: Interner::new(), edges: vec![] }
+    }
+}
+
+impl<E> GraphBuilder<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, label: &str) -> u32 {
+        self.interner.intern(label)
+    }
+
+    pub fn add_edge(&mut self, from: &str, to: &str, edge: E) -> &mut Self {
+        let from = self.interner.intern(from);
+        let to = self.interner.intern(to);
+        self.edges.push((from, to, edge));
+        self
+    }
+
+    pub fn build(self) -> Graph<E> {
+        let mut adjacency: Vec<Vec<(u32, E)>> = (0..self.interner.len()).map(|_| vec![]).collect();
+
+        for (from, to, edge) in self.edges {
+            adjacency[from as usize].push((to, edge));
+        }
+
+        Graph { interner: self.interner, adjacency }
+    }
+}
+
+/// A directed graph whose nodes are dense ids. Edges keep the order they were added in.
+#[derive(Debug, Clone)]
+pub struct Graph<E> {
+    interner: Interner,
+    adjacency: Vec<Vec<(u32, E)>>,
+}
+
+impl<E> Default for Graph<E> {
+    fn default() -> Self {
+        GraphBuilder::new().build()
+    }
+}
+
+impl<E> Graph<E> {
+    pub fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn id(&self, label: &str) -> Option<u32> {
+        self.interner.get(label)
+    }
+
+    pub fn label(&self, id: u32) -> &str {
+        self.interner.label(id)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.interner.iter()
+    }
+
+    pub fn edges(&self, id: u32) -> &[(u32, E)] {
+        &self.adjacency[id as usize]
+    }
+
+    /// The first node reached from `id` along an edge labelled `edge`.
+    pub fn follow(&self, id: u32, edge: &E) -> Option<u32>
+    where
+        E: PartialEq,
+    {
+        self.edges(id).iter().find(|(_, e)| e == edge).map(|&(to, _)| to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interns_densely() {
+        let mut interner = Interner::new();
+
+        assert_eq!(0, interner.intern("AAA"));
+        assert_eq!(1, interner.intern("BBB"));
+        assert_eq!(0, interner.intern("AAA"));
+        assert_eq!(Some(1), interner.get("BBB"));
+        assert_eq!(None, interner.get("CCC"));
+        assert_eq!("BBB", interner.label(1));
+        assert_eq!(2, interner.len());
+    }
+
+    #[test]
+    fn builds_adjacency() {
+        let mut builder = GraphBuilder::new();
+        builder
+            .add_edge("AAA", "BBB", 'L')
+            .add_edge("AAA", "CCC", 'R')
+            .add_edge("BBB", "AAA", 'L');
+        builder.add_node("DDD");
+
+        let graph = builder.build();
+        let a = graph.id("AAA").unwrap();
+
+        assert_eq!(4, graph.node_count());
+        assert_eq!(Some("CCC"), graph.follow(a, &'R').map(|id| graph.label(id)));
+        assert_eq!(None, graph.follow(graph.id("CCC").unwrap(), &'L'));
+        assert!(graph.edges(graph.id("DDD").unwrap()).is_empty());
+    }
+}
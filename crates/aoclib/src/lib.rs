@@ -1,54 +1,99 @@
 use std::fmt::Display;
-use std::fs::read_to_string;
+use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, Instant};
-use regex::Regex;
+use std::time::Duration;
+use dot::Dot;
+use examples::{Example, ExampleManifest};
+use report::{ConsoleReporter, Observer};
+use visualize::{Frame, Visualize};
+use worker::{Finished, Worker};
+
+// Lets code generated by aoclib_derive, which names ::aoclib, compile inside this crate
+extern crate self as aoclib;
+
+pub mod alloc;
+pub mod answers;
+pub mod cli;
+pub mod doctor;
+pub mod dot;
+pub mod examples;
+pub mod extract;
+pub mod fetch;
+pub mod geometry;
+pub mod grid;
+pub mod guesses;
+pub mod html;
+pub mod input;
+pub mod intern;
+pub mod math;
+pub mod memo;
+pub mod number_theory;
+pub mod numbers;
+pub mod poly;
+pub mod readme;
+pub mod records;
+pub mod render;
+pub mod report;
+pub mod sandbox;
+pub mod submit;
+#[cfg(test)]
+mod testing;
+pub mod visualize;
+pub mod worker;
+
+pub use cli::{Command, Options};
+pub use input::Input;
+pub use math::{gcd, lcm, lcm_of};
+pub use numbers::{ints, ints_n, signed_ints, uints, uints_n};
+pub use worker::{Outcome, Phase, PhaseReport};
 
 pub fn read_lines<T: AsRef<Path>>(pathname: T) -> Vec<String> {
     read(pathname, "\n")
 }
 
 pub fn read_groups<T: AsRef<Path>>(pathname: T) -> Vec<String> {
-    let regex = Regex::new(r"\n\n|\r\n\r\n").unwrap();
-
-    let contents = read_full(pathname);
-    regex
-        .split(&contents)
-        .filter(|s| !s.is_empty())
-        .map(|s|s.to_string())
-        .collect()
+    groups_of(Input::load(pathname))
 }
 
 pub fn read_full<T: AsRef<Path>>(pathname: T) -> String {
-    read_to_string(pathname)
-        .expect("unable to open file")
+    full_of(Input::load(pathname))
 }
 
 pub fn read<T: AsRef<Path>>(pathname: T, separator: &str) -> Vec<String> {
-    read_to_string(pathname)
-        .expect("unable to open file")
-        .split(separator)
-        .filter(|s| !s.is_empty())
-        .map(|s| s.to_string())
-        .collect()
+    fields_of(Input::load(pathname), separator)
 }
 
-pub fn lcm_of(list: Vec<i64>) -> i64 {
-    list.iter().fold(1, |acc, x| lcm(acc, *x))
+/// Like [`read_lines`], with trailing whitespace stripped from every line first.
+pub fn read_lines_trimmed<T: AsRef<Path>>(pathname: T) -> Vec<String> {
+    read_trimmed(pathname, "\n")
 }
 
-pub fn gcd(a: i64, b: i64) -> i64 {
-    // Calculate the greatest common divisor using the Euclidean Algorithm
-    if b == 0 { a } else { gcd(b, a % b) }
+/// Like [`read_groups`], with trailing whitespace stripped from every line first, so lines of
+/// only spaces separate groups too.
+pub fn read_groups_trimmed<T: AsRef<Path>>(pathname: T) -> Vec<String> {
+    groups_of(Input::load(pathname).trim_trailing_whitespace())
 }
 
-pub fn lcm(a: i64, b: i64) -> i64 {
-    // Calculate the lowest common multiple using the Greatest Common Divisor
-    if a == 0 || b == 0 {
-        0
-    } else {
-        (a * b) / gcd(a,b)
-    }
+/// Like [`read_full`], with trailing whitespace stripped from every line first.
+pub fn read_full_trimmed<T: AsRef<Path>>(pathname: T) -> String {
+    full_of(Input::load(pathname).trim_trailing_whitespace())
+}
+
+/// Like [`read`], with trailing whitespace stripped from every line first.
+pub fn read_trimmed<T: AsRef<Path>>(pathname: T, separator: &str) -> Vec<String> {
+    fields_of(Input::load(pathname).trim_trailing_whitespace(), separator)
+}
+
+fn groups_of(input: Input) -> Vec<String> {
+    input.groups().map(|s| s.to_string()).collect()
+}
+
+fn full_of(input: Input) -> String {
+    input.as_str().trim_end_matches('\n').to_string()
+}
+
+fn fields_of(input: Input, separator: &str) -> Vec<String> {
+    input.fields(separator).map(|s| s.to_string()).collect()
 }
 
 pub fn get_repo_root() -> PathBuf {
@@ -61,70 +106,310 @@ pub fn get_repo_root() -> PathBuf {
         .to_path_buf()
 }
 
+/// Where a day's puzzle input lives, relative to [`get_repo_root`].
+pub fn input_file(year: usize, day: usize) -> String {
+    format!("aoc/crates/aoc{year}/input/{year}-{day:02}.txt")
+}
+
 pub fn output<T: Display>(output: T) -> Vec<String> {
     vec![format!("{}", output)]
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Selector {
     All,
     One(usize),
+    #[default]
     Last,
 }
 
+impl Selector {
+    /// The days this selects, counting from 1, out of `count` solutions. A single day may be past
+    /// `count`, for commands like fetch that don't need a solution.
+    pub fn days(&self, count: usize) -> Vec<usize> {
+        match self {
+            Selector::All => (1..=count).collect(),
+            Selector::One(day) => vec![*day],
+            Selector::Last if count == 0 => vec![],
+            Selector::Last => vec![count],
+        }
+    }
+
+    /// Like [`Selector::days`], but fails if a selected day has no solution among `count`.
+    pub fn registered(&self, count: usize) -> Result<Vec<usize>, String> {
+        let days = self.days(count);
+        match days.iter().find(|&&day| day == 0 || day > count) {
+            Some(day) if count == 0 => Err(format!("Day {day} isn't registered, no days are")),
+            Some(day) => Err(format!("Day {day} isn't registered, the last one is day {count}")),
+            None => Ok(days),
+        }
+    }
+}
+
 pub trait Runner {
     fn name(&self) -> (usize, usize);
     fn set_input(&mut self, input: &str);
     fn parse(&mut self);
     fn part1(&mut self) -> i64;
     fn part2(&mut self) -> i64;
+
+    /// Solutions that implement [`Visualize`] return themselves here so the runner can show
+    /// their frames.
+    fn visualize(&mut self) -> Option<&mut dyn Visualize> {
+        None
+    }
+
+    /// The parsed input as a graph, for solutions whose puzzle is graph shaped.
+    fn graph(&self) -> Option<Dot> {
+        None
+    }
+}
+
+pub fn run_solution<T: Runner + Send + 'static>(solution: T) -> Vec<PhaseReport> {
+    run_solution_with(Box::new(solution), &Options::default())
+}
+
+/// Runs `solution` with its output on the console.
+pub fn run_solution_with(solution: Box<dyn Runner + Send>, options: &Options) -> Vec<PhaseReport> {
+    let mut console = ConsoleReporter;
+    let reports = run_solution_observed(solution, options, &mut console);
+    console.run_finished();
+    reports
+}
+
+/// Makes a fresh instance of one day's solution.
+pub type Factory = fn() -> Box<dyn Runner + Send>;
+
+/// Carries out `options.command` for the registered `days`.
+pub fn run(days: &[Factory], options: &Options) {
+    match options.command {
+        Command::Run => run_selected(days, options),
+        Command::Readme => {
+            if let Err(error) = readme::generate(days, options) {
+                eprintln!("Unable to update the README: {error}");
+            }
+        }
+        Command::Doctor => {
+            if !doctor::run(days) {
+                std::process::exit(1);
+            }
+        }
+        Command::Fetch => {
+            let Some(factory) = days.first() else { return };
+            if !fetch::run(factory().name().0, &options.selector.days(days.len())) {
+                std::process::exit(1);
+            }
+        }
+        Command::Submit => {
+            if !submit::run(days, options) {
+                std::process::exit(1);
+            }
+        }
+        Command::Extract => {
+            if !extract::run(days, options) {
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Runs the days `options.selector` picks out of `days`, in a sandbox if asked to, telling the
+/// observers `options` asks for how each goes. Exits with an error if a sandboxed day didn't
+/// complete, or with [`sandbox::PHASE_FAILED`] if a phase panicked or timed out.
+pub fn run_selected(days: &[Factory], options: &Options) {
+    let selected = match options.selector.registered(days.len()) {
+        Ok(selected) => selected,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(limits) = options.sandbox {
+        let mut completed = true;
+        for day in selected {
+            match sandbox::run_day(day, limits, options) {
+                Ok(result) => completed &= result.status == sandbox::ChildStatus::Completed,
+                Err(error) => {
+                    eprintln!("Unable to run day {day}: {error}");
+                    completed = false;
+                }
+            }
+        }
+        if !completed {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut observers = options.observers();
+    let mut failed = false;
+    for day in selected {
+        let reports = if options.example {
+            run_example(days[day - 1], options, &mut observers)
+        } else {
+            run_solution_observed(days[day - 1](), options, &mut observers)
+        };
+        failed |= reports.iter().any(|report| report.outcome.is_failure());
+    }
+    observers.run_finished();
+
+    if failed {
+        std::process::exit(sandbox::PHASE_FAILED);
+    }
 }
 
-pub fn run_solution<T: Runner + ?Sized>(solution: &mut T) {
+/// Runs `solution` on its puzzle input.
+pub fn run_solution_observed(
+    solution: Box<dyn Runner + Send>,
+    options: &Options,
+    observer: &mut dyn Observer,
+) -> Vec<PhaseReport> {
     let name = solution.name();
-    println!("---- {}, Day {} ----", name.0, name.1);
+    observer.day_started(name);
 
-    solution.set_input(&format!("aoc/crates/aoc{}/input/{}-{:02}.txt", name.0, name.0, name.1));
+    let input = input_file(name.0, name.1);
+    run_phases(solution, &input, &Phase::ALL, [None, None], options, observer)
+}
 
-    let start = Instant::now();
-    solution.parse();
-    let parse_time = start.elapsed();
-    println!("{} Parsing", get_duration_string(parse_time));
+/// Runs each part of a day against its example from the manifest. Parts that share an example
+/// run one after the other on the same instance, as they do on the puzzle input, so state one
+/// part leaves behind shows up in the other. Parts with examples of their own each get a fresh
+/// instance.
+pub fn run_example(factory: Factory, options: &Options, observer: &mut dyn Observer) -> Vec<PhaseReport> {
+    let name = factory().name();
+    observer.day_started(name);
 
-    let start = Instant::now();
-    let p1 = solution.part1();
-    let p1_str = vec![format!("{p1}")];
-    let p1_time = start.elapsed();
-    print_solution(1, &p1_str, p1_time);
+    let manifest = match ExampleManifest::load(examples::manifest_path(name.0)) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            eprintln!("Unable to read the example manifest: {error}");
+            return vec![];
+        }
+    };
 
-    let start = Instant::now();
-    let p2 = solution.part2();
-    let p2_str = vec![format!("{p2}")];
-    let p2_time = start.elapsed();
-    print_solution(2, &p2_str, p2_time);
+    let examples = [1, 2].map(|part| {
+        let example = manifest.get(name.1, part);
+        if example.is_none() {
+            eprintln!("No example recorded for day {} part {part}", name.1);
+        }
+        example
+    });
+    let expected = examples.map(|example| example.map(|example| example.expected));
+    let input = |example: &Example| format!("aoc/crates/aoc{}/test/{}", name.0, example.file);
+
+    match examples {
+        [Some(first), Some(second)] if first.file == second.file => {
+            run_phases(factory(), &input(first), &Phase::ALL, expected, options, observer)
+        }
+        _ => {
+            let mut reports = vec![];
+            for (example, phase) in examples.into_iter().zip([Phase::Part1, Phase::Part2]) {
+                if let Some(example) = example {
+                    reports.extend(run_phases(factory(), &input(example), &[Phase::Parse, phase], expected, options, observer));
+                }
+            }
+            reports
+        }
+    }
 }
 
-fn print_solution(which: usize, output: &[String], duration: Duration) {
+/// Runs `phases` of `solution` on a worker thread, so a phase that panics or runs past
+/// `options.timeout` is reported as failed rather than stopping the whole run.
+fn run_phases(
+    mut solution: Box<dyn Runner + Send>,
+    input: &str,
+    phases: &[Phase],
+    expected: [Option<i64>; 2],
+    options: &Options,
+    observer: &mut dyn Observer,
+) -> Vec<PhaseReport> {
+    let name = solution.name();
+    solution.set_input(input);
+
+    if options.visualize.is_some() || options.export.is_some() {
+        if let Some(visualize) = solution.visualize() {
+            visualize.frames().enable();
+        }
+    }
+
+    let mut worker = Worker::spawn(solution, options.dot.is_some());
+    let mut exported = vec![];
+    let mut reports: Vec<PhaseReport> = vec![];
 
+    for &phase in phases {
+        let parse_failed = reports.first().is_some_and(|report| report.outcome != Outcome::Parsed);
+        let mut finished = if parse_failed {
+            Finished {
+                report: PhaseReport {
+                    phase,
+                    outcome: Outcome::Skipped,
+                    duration: Duration::ZERO,
+                    alloc: None,
+                    expected: None,
+                },
+                frames: vec![],
+                graph: None,
+            }
+        } else {
+            worker.run(phase, options.timeout)
+        };
 
-    let mut i = output.iter();
+        if let Some(renderer) = &options.visualize {
+            renderer.render(&finished.frames);
+        }
+        if options.export.is_some() {
+            exported.extend(finished.frames);
+        }
+        if let (Some(dir), Some(dot)) = (&options.dot, finished.graph) {
+            export_graph(name, &dot, dir);
+        }
 
-    println!(
-        "{} Part {which}: {}",
-        get_duration_string(duration),
-        i.next().unwrap()
-    );
+        finished.report.expected = match phase {
+            Phase::Parse => None,
+            Phase::Part1 => expected[0],
+            Phase::Part2 => expected[1],
+        };
+        observer.phase_finished(name, &finished.report);
+        if finished.report.outcome.is_failure() {
+            observer.phase_failed(name, &finished.report);
+        }
+        reports.push(finished.report);
+    }
 
-    for line in i {
-        println!("{:16}{line}", "");
+    if let Some(dir) = &options.export {
+        export_frames(dir, name, &exported);
     }
+
+    reports
 }
 
-fn get_duration_string(duration: Duration) -> String {
-    let seconds = duration.as_secs();
-    let milliseconds = duration.subsec_millis();
-    let microseconds = duration.subsec_micros() % 1000;
+fn export_graph(name: (usize, usize), dot: &Dot, dir: &Path) {
+    let path = dir.join(format!("{}-{:02}.dot", name.0, name.1));
+
+    match create_dir_all(dir).and_then(|_| dot.write(&path)) {
+        Ok(()) => println!("Exported graph to {}", path.display()),
+        Err(error) => eprintln!("Unable to export graph: {error}"),
+    }
+}
+
+/// Writes every frame as `<dir>/<year>-<day>.gif` and the final one as `<dir>/<year>-<day>.png`.
+fn export_frames(dir: &Path, name: (usize, usize), frames: &[Frame]) {
+    let Some(last) = frames.last() else {
+        return;
+    };
+    let stem = dir.join(format!("{}-{:02}", name.0, name.1));
+
+    let result = create_dir_all(dir).map_err(render::RenderError::from).and_then(|_| {
+        let images: Vec<_> = frames.iter().map(|frame| render::Image::from_frame(frame, render::EXPORT_SCALE)).collect();
+        render::write_gif(stem.with_extension("gif"), &images, 10)?;
+        render::write_png(stem.with_extension("png"), &render::Image::from_frame(last, render::EXPORT_SCALE))
+    });
 
-    format!("{seconds:3}.{milliseconds:03}.{microseconds:03}")
+    match result {
+        Ok(()) => println!("Exported {} frames to {}", frames.len(), stem.with_extension("gif").display()),
+        Err(error) => eprintln!("Unable to export frames: {error}"),
+    }
 }
 
 pub fn transpose(map: &[Vec<bool>]) -> Vec<Vec<bool>> {
@@ -136,4 +421,62 @@ pub fn transpose(map: &[Vec<bool>]) -> Vec<Vec<bool>> {
     let col_count = map[0].len();
 
     (0..col_count).map(|col_index| (0..row_count).map(|row_index| map[row_index][col_index]).collect()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testing::TempPath;
+
+    const CRLF: &str = "\u{feff}ab\r\ncd\r\n\r\nef\r\n";
+
+    #[test]
+    fn selects_registered_days() {
+        assert_eq!(Ok(vec![1, 2, 3]), Selector::All.registered(3));
+        assert_eq!(Ok(vec![3]), Selector::Last.registered(3));
+        assert_eq!(Ok(vec![2]), Selector::One(2).registered(3));
+        assert_eq!(Err("Day 20 isn't registered, the last one is day 15".to_string()), Selector::One(20).registered(15));
+
+        assert_eq!(Vec::<usize>::new(), Selector::Last.days(0));
+        assert_eq!(Ok(vec![]), Selector::Last.registered(0));
+        assert!(Selector::One(1).registered(0).is_err());
+        assert_eq!(vec![20], Selector::One(20).days(15));
+    }
+
+    #[test]
+    fn read_lines_normalises() {
+        let path = TempPath::with_contents("lines.txt", CRLF);
+        assert_eq!(vec!["ab", "cd", "ef"], read_lines(&path));
+    }
+
+    #[test]
+    fn read_groups_normalises() {
+        let path = TempPath::with_contents("groups.txt", CRLF);
+        assert_eq!(vec!["ab\ncd", "ef"], read_groups(&path));
+    }
+
+    #[test]
+    fn read_full_normalises() {
+        let path = TempPath::with_contents("full.txt", CRLF);
+        assert_eq!("ab\ncd\n\nef", read_full(&path));
+
+        let path = TempPath::with_contents("steps.txt", "rn=1,cm-\n");
+        assert_eq!(vec!["rn=1", "cm-"], read_full(&path).split(',').collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn read_normalises() {
+        let path = TempPath::with_contents("fields.txt", "\u{feff}1,2\r\n,3");
+        assert_eq!(vec!["1", "2\n", "3"], read(&path, ","));
+    }
+
+    #[test]
+    fn readers_trim_on_request() {
+        let path = TempPath::with_contents("trailing.txt", "ab  \r\ncd\t\r\n  \r\nef \r\n");
+        assert_eq!(vec!["ab  ", "cd\t", "  ", "ef "], read_lines(&path));
+        assert_eq!(vec!["ab", "cd", "ef"], read_lines_trimmed(&path));
+        assert_eq!(vec!["ab\ncd", "ef"], read_groups_trimmed(&path));
+        assert_eq!("ab\ncd\n\nef", read_full_trimmed(&path));
+        assert_eq!(vec!["ab\ncd\n\nef\n"], read_trimmed(&path, ","));
+    }
 }
\ No newline at end of file
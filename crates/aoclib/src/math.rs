@@ -0,0 +1,179 @@
+use std::fmt::{Display, Formatter};
+use num::{BigInt, CheckedMul, CheckedSub, Integer, Signed};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    Overflow,
+    NoSolution,
+    NotInvertible,
+    ZeroModulus,
+}
+
+impl Display for MathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MathError::Overflow => write!(f, "integer overflow"),
+            MathError::NoSolution => write!(f, "no solution exists"),
+            MathError::NotInvertible => write!(f, "value has no modular inverse"),
+            MathError::ZeroModulus => write!(f, "modulus is zero"),
+        }
+    }
+}
+
+impl std::error::Error for MathError {}
+
+/// Panics if the gcd doesn't fit in `T`, which only happens for a signed `MIN` and `0` or `MIN`.
+/// [`checked_gcd`] reports that as an error instead.
+pub fn gcd<T: Integer + CheckedSub + Clone>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("gcd doesn't fit in the integer type")
+}
+
+pub fn checked_gcd<T: Integer + CheckedSub + Clone>(a: T, b: T) -> Result<T, MathError> {
+    // Iterative Euclidean Algorithm, so large inputs can't blow the stack
+    let (mut a, mut b) = (a, b);
+    while !b.is_zero() {
+        // MIN % -1 overflows, and anything shares only a unit with ±1 anyway
+        if is_unit(&b) {
+            return Ok(T::one());
+        }
+        let r = a.mod_floor(&b);
+        a = b;
+        b = r;
+    }
+    checked_abs(a)
+}
+
+/// Panics if the lcm doesn't fit in `T`; [`checked_lcm`] reports that as an error instead.
+pub fn lcm<T: Integer + CheckedMul + CheckedSub + Clone>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm doesn't fit in the integer type")
+}
+
+pub fn checked_lcm<T: Integer + CheckedMul + CheckedSub + Clone>(a: T, b: T) -> Result<T, MathError> {
+    // Divide before multiplying to keep the intermediate value small
+    if a.is_zero() || b.is_zero() {
+        Ok(T::zero())
+    } else {
+        let g = checked_gcd(a.clone(), b.clone())?;
+        checked_abs((a / g).checked_mul(&b).ok_or(MathError::Overflow)?)
+    }
+}
+
+pub fn gcd_of<T: Integer + CheckedSub + Clone, I: IntoIterator<Item = T>>(list: I) -> T {
+    list.into_iter().fold(T::zero(), gcd)
+}
+
+pub fn lcm_of<T: Integer + CheckedMul + CheckedSub + Clone, I: IntoIterator<Item = T>>(list: I) -> T {
+    list.into_iter().fold(T::one(), lcm)
+}
+
+pub fn checked_lcm_of<T, I>(list: I) -> Result<T, MathError>
+where
+    T: Integer + CheckedMul + CheckedSub + Clone,
+    I: IntoIterator<Item = T>,
+{
+    list.into_iter().try_fold(T::one(), checked_lcm)
+}
+
+pub fn lcm_of_u128<T: Into<u128>, I: IntoIterator<Item = T>>(list: I) -> Result<u128, MathError> {
+    checked_lcm_of(list.into_iter().map(Into::into))
+}
+
+pub fn lcm_of_big<T: Into<BigInt>, I: IntoIterator<Item = T>>(list: I) -> BigInt {
+    lcm_of(list.into_iter().map(Into::into))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is the gcd of `a` and `b`.
+pub fn extended_gcd<T: Integer + Signed + Clone>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::one(), T::zero());
+    let (mut old_t, mut t) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let q = old_r.div_floor(&r);
+        (old_r, r) = (r.clone(), old_r - q.clone() * r);
+        (old_s, s) = (s.clone(), old_s - q.clone() * s);
+        (old_t, t) = (t.clone(), old_t - q * t);
+    }
+
+    if old_r.is_negative() {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+fn checked_abs<T: Integer + CheckedSub>(value: T) -> Result<T, MathError> {
+    if value < T::zero() {
+        T::zero().checked_sub(&value).ok_or(MathError::Overflow)
+    } else {
+        Ok(value)
+    }
+}
+
+fn is_unit<T: Integer + CheckedSub>(value: &T) -> bool {
+    value.is_one() || T::zero().checked_sub(value).is_some_and(|negated| negated.is_one())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(6, gcd(48i64, 18));
+        assert_eq!(6, gcd(-48i64, 18));
+        assert_eq!(144, lcm(48u32, 18));
+        assert_eq!(0, lcm(0i64, 18));
+        assert_eq!(6, gcd_of([12u64, 18, 30]));
+        assert_eq!(60, lcm_of(vec![3i64, 4, 5]));
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let big = i64::MAX / 2;
+        assert_eq!(Err(MathError::Overflow), checked_lcm(big, big - 1));
+        assert_eq!(Err(MathError::Overflow), checked_lcm_of([u8::MAX, 254]));
+        assert_eq!(Ok(u8::MAX as u128 * 254), lcm_of_u128([u8::MAX, 254]));
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm doesn't fit in the integer type")]
+    fn lcm_panics_on_overflow() {
+        let big = i64::MAX / 2;
+        lcm(big, big - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm doesn't fit in the integer type")]
+    fn lcm_of_panics_on_overflow() {
+        lcm_of([u8::MAX, 254]);
+    }
+
+    #[test]
+    fn signed_min() {
+        assert_eq!(Err(MathError::Overflow), checked_gcd(i64::MIN, 0));
+        assert_eq!(Err(MathError::Overflow), checked_gcd(i64::MIN, i64::MIN));
+        assert_eq!(Ok(1 << 62), checked_gcd(i64::MIN, 1 << 62));
+        assert_eq!(1, gcd(i64::MIN, -1));
+        assert_eq!(1, gcd(u8::MAX, 1));
+        assert_eq!(Err(MathError::Overflow), checked_lcm(i64::MIN, 1));
+    }
+
+    #[test]
+    fn big_lcm() {
+        let primes = [1_000_000_007u64, 998_244_353, 1_000_000_009];
+        let expected = BigInt::from(1_000_000_007u64) * 998_244_353u64 * 1_000_000_009u64;
+        assert_eq!(expected, lcm_of_big(primes));
+    }
+
+    #[test]
+    fn extended() {
+        let (g, x, y) = extended_gcd(240i64, 46);
+        assert_eq!(2, g);
+        assert_eq!(g, 240 * x + 46 * y);
+
+        let (g, x, y) = extended_gcd(-7i64, 3);
+        assert_eq!(1, g);
+        assert_eq!(g, -7 * x + 3 * y);
+    }
+}
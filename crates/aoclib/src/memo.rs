@@ -0,0 +1,155 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+/// A cache for memoized recursion that counts its hits and misses.
+///
+/// The compute closure receives the memo itself, so recursive calls go through the same cache.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    stats: MemoStats,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl MemoStats {
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hits, {} misses ({:.1}% hit rate)", self.hits, self.misses, self.hit_rate() * 100.0)
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo { cache: HashMap::new(), stats: MemoStats::default() }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks up a cached value, counting a hit or a miss.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let result = self.cache.get(key).cloned();
+        match result {
+            Some(_) => self.stats.hits += 1,
+            None => self.stats.misses += 1,
+        }
+        result
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> V {
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        match self.get(&key) {
+            Some(value) => value,
+            None => {
+                let value = compute(self);
+                self.insert(key, value)
+            }
+        }
+    }
+
+    /// Like [`Memo::get_or_compute`], but looks up a borrowed key and only converts it to an
+    /// owned key when the value has to be computed.
+    pub fn get_or_compute_borrowed<Q, F>(&mut self, key: &Q, compute: F) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+        F: FnOnce(&mut Self) -> V,
+    {
+        match self.get(key) {
+            Some(value) => value,
+            None => {
+                let value = compute(self);
+                self.insert(key.to_owned(), value)
+            }
+        }
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = MemoStats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let mut memo: Memo<(usize, usize), u64> = Memo::new();
+
+        assert_eq!(3, memo.get_or_compute((1, 2), |_| 3));
+        assert_eq!(3, memo.get_or_compute((1, 2), |_| unreachable!()));
+        assert_eq!(None, memo.get(&(2, 1)));
+
+        assert_eq!(MemoStats { hits: 1, misses: 2 }, memo.stats());
+        assert_eq!(1, memo.len());
+    }
+
+    #[test]
+    fn recursion() {
+        fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+            if n < 2 {
+                return n;
+            }
+            memo.get_or_compute(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+        }
+
+        let mut memo = Memo::new();
+
+        assert_eq!(12586269025, fib(50, &mut memo));
+        assert_eq!(49, memo.stats().misses);
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        let mut memo: Memo<String, usize> = Memo::new();
+
+        assert_eq!(5, memo.get_or_compute_borrowed("hello", |_| 5));
+        assert_eq!(5, memo.get_or_compute_borrowed("hello", |_| unreachable!()));
+        assert_eq!("1 hits, 1 misses (50.0% hit rate)", memo.stats().to_string());
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(MemoStats::default(), memo.stats());
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use crate::math::{extended_gcd, MathError};
+
+/// Computes `base.pow(exp) % modulus` without overflowing, for any `modulus` up to `u64::MAX`.
+/// Panics if `modulus` is zero.
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut exp = exp;
+    let mut result = 1u128;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    result as u64
+}
+
+/// Returns `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`.
+pub fn mod_inverse(a: i64, modulus: i64) -> Result<i64, MathError> {
+    if modulus == 0 {
+        return Err(MathError::ZeroModulus);
+    }
+    let modulus = (modulus as i128).abs();
+    let (g, x, _) = extended_gcd(a as i128, modulus);
+
+    if g != 1 {
+        return Err(MathError::NotInvertible);
+    }
+
+    Ok(x.rem_euclid(modulus) as i64)
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese Remainder Theorem.
+///
+/// The moduli don't need to be coprime. Returns the smallest non-negative solution together with
+/// the modulus of the combined system (the lcm of all moduli), so every solution is
+/// `x + k * modulus`.
+pub fn crt<I: IntoIterator<Item = (i64, i64)>>(congruences: I) -> Result<(i64, i64), MathError> {
+    let mut x = 0i128;
+    let mut m = 1i128;
+
+    for (residue, modulus) in congruences {
+        if modulus == 0 {
+            return Err(MathError::ZeroModulus);
+        }
+        let modulus = (modulus as i128).abs();
+        let residue = (residue as i128).rem_euclid(modulus);
+
+        let (g, p, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+
+        if diff % g != 0 {
+            return Err(MathError::NoSolution);
+        }
+
+        let step = modulus / g;
+        let k = (diff / g % step * p).rem_euclid(step);
+
+        x += m * k;
+        m *= step;
+
+        if m > i64::MAX as i128 {
+            return Err(MathError::Overflow);
+        }
+        x = x.rem_euclid(m);
+    }
+
+    Ok((x as i64, m as i64))
+}
+
+/// An integer modulo the compile time constant `M`. `ModInt<0>` fails to compile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> Default for ModInt<M> {
+    fn default() -> Self {
+        ModInt::new(0)
+    }
+}
+
+impl<const M: u64> ModInt<M> {
+    const NONZERO: () = assert!(M > 0, "ModInt needs a non-zero modulus");
+
+    pub fn new(value: u64) -> Self {
+        let () = Self::NONZERO;
+        ModInt(value % M)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(&self, exp: u64) -> Self {
+        ModInt(mod_pow(self.0, exp, M))
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let (g, x, _) = extended_gcd(self.0 as i128, M as i128);
+
+        if g == 1 {
+            Some(ModInt(x.rem_euclid(M as i128) as u64))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        ModInt::new(value)
+    }
+}
+
+impl<const M: u64> From<i64> for ModInt<M> {
+    fn from(value: i64) -> Self {
+        let () = Self::NONZERO;
+        ModInt((value as i128).rem_euclid(M as i128) as u64)
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        ModInt(((self.0 as u128 + rhs.0 as u128) % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        ModInt(((self.0 as u128 + M as u128 - rhs.0 as u128) % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        ModInt((self.0 as u128 * rhs.0 as u128 % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse().expect("Divisor has no inverse for this modulus")
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ModInt::default() - self
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const M: u64> DivAssign for ModInt<M> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pow_and_inverse() {
+        assert_eq!(445, mod_pow(4, 13, 497));
+        assert_eq!(0, mod_pow(5, 0, 1));
+        assert_eq!(1, mod_pow(u64::MAX - 1, u64::MAX - 1, u64::MAX));
+
+        assert_eq!(Ok(4), mod_inverse(3, 11));
+        assert_eq!(Ok(7), mod_inverse(-3, 11));
+        assert_eq!(Err(MathError::NotInvertible), mod_inverse(6, 9));
+        assert_eq!(Err(MathError::ZeroModulus), mod_inverse(3, 0));
+
+        let inverse = mod_inverse(3, i64::MIN).unwrap();
+        assert_eq!(1, (3 * inverse as i128).rem_euclid(1 << 63));
+        assert_eq!(Err(MathError::NotInvertible), mod_inverse(2, i64::MIN));
+    }
+
+    #[test]
+    fn coprime_crt() {
+        assert_eq!(Ok((23, 105)), crt([(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Ok((0, 1)), crt([]));
+    }
+
+    #[test]
+    fn non_coprime_crt() {
+        assert_eq!(Ok((10, 12)), crt([(4, 6), (2, 4)]));
+        assert_eq!(Err(MathError::NoSolution), crt([(1, 6), (2, 4)]));
+        assert_eq!(Err(MathError::ZeroModulus), crt([(1, 6), (2, 0)]));
+        assert_eq!(Err(MathError::Overflow), crt([(0, i64::MAX), (0, i64::MAX - 1)]));
+    }
+
+    #[test]
+    fn mod_int() {
+        type M7 = ModInt<7>;
+
+        let a = M7::new(5);
+        let b = M7::from(-4i64);
+
+        assert_eq!(3, b.value());
+        assert_eq!(1, (a + b).value());
+        assert_eq!(2, (a - b).value());
+        assert_eq!(1, (a * b).value());
+        assert_eq!(4, (a / b).value());
+        assert_eq!(2, (-a).value());
+        assert_eq!(3, a.pow(5).value());
+        assert_eq!(None, ModInt::<8>::new(4).inverse());
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// Every run of digits in `text`, ignoring any signs.
+pub fn uints<T>(text: &str) -> impl Iterator<Item = T> + '_
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    tokens(text, false).map(|token| token.parse().expect("number out of range"))
+}
+
+/// Every integer in `text`. A `-` directly before a number makes it negative, unless the `-`
+/// itself follows a letter or digit, so ranges like `3-5` read as `3` and `5`.
+pub fn signed_ints<T>(text: &str) -> impl Iterator<Item = T> + '_
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    tokens(text, true).map(|token| token.parse().expect("number out of range"))
+}
+
+/// Every integer in `text` as an `i64`, see [`signed_ints`].
+pub fn ints(text: &str) -> impl Iterator<Item = i64> + '_ {
+    signed_ints(text)
+}
+
+/// Exactly `N` integers from `text`, panicking if there are more or fewer.
+pub fn ints_n<const N: usize>(text: &str) -> [i64; N] {
+    collect_n(text, ints(text))
+}
+
+/// Exactly `N` unsigned integers from `text`, panicking if there are more or fewer.
+pub fn uints_n<const N: usize>(text: &str) -> [u64; N] {
+    collect_n(text, uints(text))
+}
+
+fn collect_n<T: Debug, const N: usize>(text: &str, numbers: impl Iterator<Item = T>) -> [T; N] {
+    numbers
+        .collect::<Vec<T>>()
+        .try_into()
+        .unwrap_or_else(|numbers: Vec<T>| panic!("Expected {N} numbers but found {} in {text:?}", numbers.len()))
+}
+
+fn tokens(text: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+
+        let mut start = i;
+        if signed && start > 0 && bytes[start - 1] == b'-' && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric()) {
+            start -= 1;
+        }
+
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        Some(&text[start..i])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_numbers() {
+        assert_eq!(vec![7, 15, 30], uints::<u32>("Time:      7  15   30").collect::<Vec<_>>());
+        assert_eq!(vec![1, 41, 48, 83], uints::<i32>("Card   1: 41 48 | 83").collect::<Vec<_>>());
+        assert_eq!(0, ints("no numbers here").count());
+    }
+
+    #[test]
+    fn handles_signs() {
+        assert_eq!(vec![10, -3, 0, -7], ints("10 -3 0 -7").collect::<Vec<_>>());
+        assert_eq!(vec![-3, 4], ints("x=-3, y=4").collect::<Vec<_>>());
+        assert_eq!(vec![3, 5], ints("3-5").collect::<Vec<_>>());
+        assert_eq!(vec![3, 5], uints::<u8>("-3 -5").collect::<Vec<_>>());
+        assert_eq!(vec![-12], signed_ints::<i8>("--12").collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn fixed_arity() {
+        assert_eq!([50, 98, 2], ints_n::<3>("50 98 2"));
+        assert_eq!([1, 2], uints_n::<2>("p=1,-2"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected 3 numbers but found 2")]
+    fn wrong_arity() {
+        ints_n::<3>("1 2");
+    }
+}
@@ -0,0 +1,283 @@
+use std::fmt::{Display, Formatter};
+use num::{BigInt, BigRational, One, Signed, ToPrimitive, Zero};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolyError {
+    Empty,
+    Diverges,
+    Overflow,
+}
+
+impl Display for PolyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolyError::Empty => write!(f, "no values to fit"),
+            PolyError::Diverges => write!(f, "differences never become constant"),
+            PolyError::Overflow => write!(f, "value doesn't fit in an i64"),
+        }
+    }
+}
+
+impl std::error::Error for PolyError {}
+
+/// A polynomial with exact rational coefficients, lowest degree first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<BigRational>,
+}
+
+impl Polynomial {
+    pub fn from_coefficients(coefficients: Vec<BigRational>) -> Self {
+        let mut poly = Polynomial { coefficients };
+        poly.trim();
+        poly
+    }
+
+    /// Fits the lowest degree polynomial `p` with `p(x) == values[x]` for every index `x`.
+    ///
+    /// Fails with [`PolyError::Diverges`] if the finite differences don't reach a constant row
+    /// of at least two values, since the sequence doesn't then confirm any polynomial.
+    pub fn fit(values: &[i64]) -> Result<Self, PolyError> {
+        Polynomial::newton(values, true)
+    }
+
+    /// Fits the polynomial of degree below `values.len()` through every value, whether or not the
+    /// differences settle. This is what extending a difference table gives when the rows run
+    /// out before reaching zero.
+    pub fn interpolate(values: &[i64]) -> Result<Self, PolyError> {
+        Polynomial::newton(values, false)
+    }
+
+    fn newton(values: &[i64], confirmed: bool) -> Result<Self, PolyError> {
+        if values.is_empty() {
+            return Err(PolyError::Empty);
+        }
+
+        // Newton's forward difference formula: p(x) = sum of Δᵏf(0) * C(x, k)
+        let mut coefficients = vec![BigRational::zero()];
+        let mut basis = vec![BigRational::one()];
+
+        for (k, delta) in leading_differences(values, confirmed)?.into_iter().enumerate() {
+            if k > 0 {
+                basis = multiply_linear(&basis, &BigRational::from_integer(BigInt::from(k - 1)));
+                let k = BigRational::from_integer(BigInt::from(k));
+                basis.iter_mut().for_each(|c| *c /= &k);
+            }
+
+            coefficients.resize(basis.len(), BigRational::zero());
+            let delta = BigRational::from_integer(delta);
+            coefficients
+                .iter_mut()
+                .zip(&basis)
+                .for_each(|(c, b)| *c += &delta * b);
+        }
+
+        Ok(Polynomial::from_coefficients(coefficients))
+    }
+
+    pub fn coefficients(&self) -> &[BigRational] {
+        &self.coefficients
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    pub fn eval_rational(&self, x: &BigRational) -> BigRational {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(BigRational::zero(), |acc, c| acc * x + c)
+    }
+
+    pub fn eval(&self, x: i64) -> BigRational {
+        self.eval_rational(&BigRational::from_integer(BigInt::from(x)))
+    }
+
+    /// Evaluates at `x`, returning `None` if the result isn't an integer or doesn't fit an `i64`.
+    pub fn eval_i64(&self, x: i64) -> Option<i64> {
+        let value = self.eval(x);
+
+        if value.is_integer() {
+            value.to_integer().to_i64()
+        } else {
+            None
+        }
+    }
+
+    fn trim(&mut self) {
+        while self.coefficients.len() > 1 && self.coefficients.last().is_some_and(Zero::is_zero) {
+            self.coefficients.pop();
+        }
+        if self.coefficients.is_empty() {
+            self.coefficients.push(BigRational::zero());
+        }
+    }
+}
+
+impl Display for Polynomial {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let terms = self.coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(power, c)| !c.is_zero() || (*power == 0 && self.degree() == 0));
+
+        for (i, (power, c)) in terms.enumerate() {
+            let sign = match (i, c.is_negative()) {
+                (0, true) => "-",
+                (0, false) => "",
+                (_, true) => " - ",
+                (_, false) => " + ",
+            };
+            let c = c.abs();
+            match power {
+                0 => write!(f, "{sign}{c}")?,
+                1 => write!(f, "{sign}{c}x")?,
+                _ => write!(f, "{sign}{c}x^{power}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The value that follows `values`. The difference table is extended in `i64` when its rows reach
+/// zero without overflowing; otherwise the value comes from [`Polynomial::interpolate`].
+pub fn next_value(values: &[i64]) -> Result<i64, PolyError> {
+    let next = edges(values).and_then(|edges| edges.iter().try_fold(0i64, |next, &(_, last)| next.checked_add(last)));
+    next.map_or_else(|| interpolate_at(values, values.len() as i64), Ok)
+}
+
+/// The value that comes before `values`, found the same way as [`next_value`].
+pub fn previous_value(values: &[i64]) -> Result<i64, PolyError> {
+    let previous = edges(values)
+        .and_then(|edges| edges.iter().rev().try_fold(0i64, |previous, &(first, _)| first.checked_sub(previous)));
+    previous.map_or_else(|| interpolate_at(values, -1), Ok)
+}
+
+fn interpolate_at(values: &[i64], x: i64) -> Result<i64, PolyError> {
+    Polynomial::interpolate(values)?.eval_i64(x).ok_or(PolyError::Overflow)
+}
+
+/// Returns the first and last value of each row of the difference table above the first all-zero
+/// row, or `None` if a difference overflows or the rows run out first.
+fn edges(values: &[i64]) -> Option<Vec<(i64, i64)>> {
+    let mut row = values.to_vec();
+    let mut edges = vec![];
+
+    while row.iter().any(|&v| v != 0) {
+        edges.push((row[0], row[row.len() - 1]));
+        row = row.windows(2).map(|w| w[1].checked_sub(w[0])).collect::<Option<_>>()?;
+    }
+
+    (!row.is_empty()).then_some(edges)
+}
+
+/// Returns the first value of each row of the difference table, down to the first constant row.
+/// Unless `confirmed` is set, running out of rows first ends the table rather than failing.
+fn leading_differences(values: &[i64], confirmed: bool) -> Result<Vec<BigInt>, PolyError> {
+    let mut row = values.iter().map(|&v| BigInt::from(v)).collect::<Vec<BigInt>>();
+    let mut leading = vec![];
+
+    loop {
+        if row.len() < 2 {
+            if confirmed {
+                return Err(PolyError::Diverges);
+            }
+            leading.extend(row.first().cloned());
+            return Ok(leading);
+        }
+
+        leading.push(row[0].clone());
+
+        if row.iter().all(|v| *v == row[0]) {
+            return Ok(leading);
+        }
+
+        row = row.windows(2).map(|w| &w[1] - &w[0]).collect();
+    }
+}
+
+/// Multiplies `poly` by `(x - root)`.
+fn multiply_linear(poly: &[BigRational], root: &BigRational) -> Vec<BigRational> {
+    let mut result = vec![BigRational::zero(); poly.len() + 1];
+
+    for (power, c) in poly.iter().enumerate() {
+        result[power + 1] += c;
+        result[power] -= c * root;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(numer: i64, denom: i64) -> BigRational {
+        BigRational::new(BigInt::from(numer), BigInt::from(denom))
+    }
+
+    #[test]
+    fn fits_quadratic() {
+        // Triangular numbers: x(x+1)/2
+        let poly = Polynomial::fit(&[0, 1, 3, 6, 10, 15]).unwrap();
+
+        assert_eq!(2, poly.degree());
+        assert_eq!(&[ratio(0, 1), ratio(1, 2), ratio(1, 2)], poly.coefficients());
+        assert_eq!(Some(21), poly.eval_i64(6));
+        assert_eq!(Some(0), poly.eval_i64(-1));
+        assert_eq!(Some(1_000_000_000 * 1_000_000_001 / 2), poly.eval_i64(1_000_000_000));
+        assert_eq!(ratio(3, 8), poly.eval_rational(&ratio(1, 2)));
+        assert_eq!("1/2x^2 + 1/2x", poly.to_string());
+    }
+
+    #[test]
+    fn fits_constant() {
+        let poly = Polynomial::fit(&[7, 7]).unwrap();
+
+        assert_eq!(0, poly.degree());
+        assert_eq!(Some(7), poly.eval_i64(-100));
+        assert_eq!("7", poly.to_string());
+    }
+
+    #[test]
+    fn reports_divergence() {
+        assert_eq!(Err(PolyError::Empty), Polynomial::fit(&[]));
+        assert_eq!(Err(PolyError::Diverges), Polynomial::fit(&[1]));
+        assert_eq!(Err(PolyError::Diverges), Polynomial::fit(&[1, 2, 4, 8, 16]));
+    }
+
+    #[test]
+    fn interpolates_short_sequences() {
+        assert_eq!(Err(PolyError::Empty), Polynomial::interpolate(&[]));
+        assert_eq!(Some(5), Polynomial::interpolate(&[5]).unwrap().eval_i64(3));
+        assert_eq!(Some(5), Polynomial::interpolate(&[1, 3]).unwrap().eval_i64(2));
+        // Through 1, 2, 4, 8, 16 the next value is 31, not 32
+        assert_eq!(Some(31), Polynomial::interpolate(&[1, 2, 4, 8, 16]).unwrap().eval_i64(5));
+        assert_eq!(Polynomial::fit(&[0, 1, 3, 6]), Polynomial::interpolate(&[0, 1, 3, 6]));
+    }
+
+    #[test]
+    fn extends_sequences() {
+        assert_eq!(Ok(68), next_value(&[10, 13, 16, 21, 30, 45]));
+        assert_eq!(Ok(5), previous_value(&[10, 13, 16, 21, 30, 45]));
+        assert_eq!(Ok(0), next_value(&[0, 0]));
+        assert_eq!(Err(PolyError::Empty), next_value(&[]));
+
+        // Rows that run out before reaching zero, and differences that overflow, use the polynomial
+        assert_eq!(Ok(5), next_value(&[1, 3]));
+        assert_eq!(Ok(-1), previous_value(&[1, 3]));
+        assert_eq!(Ok(i64::MIN), next_value(&[i64::MIN, 1 << 62, 1 << 62]));
+        assert_eq!(Err(PolyError::Overflow), previous_value(&[i64::MIN, 1 << 62, 1 << 62]));
+    }
+
+    #[test]
+    fn displays_signs() {
+        let poly = Polynomial::from_coefficients(vec![ratio(-4, 1), ratio(-3, 1), ratio(0, 1), ratio(-1, 2)]);
+        assert_eq!("-1/2x^3 - 3x - 4", poly.to_string());
+        assert_eq!("2x - 1", Polynomial::fit(&[-1, 1, 3]).unwrap().to_string());
+        assert_eq!("-7", Polynomial::fit(&[-7, -7]).unwrap().to_string());
+    }
+}
@@ -0,0 +1,290 @@
+use std::fs::{read_to_string, write};
+use std::io;
+use std::time::Duration;
+use crate::answers::{store_path, AnswerStore};
+use crate::cli::Options;
+use crate::report::Observer;
+use crate::worker::{Outcome, Phase};
+use crate::{get_repo_root, run_solution_observed, Factory};
+
+pub const START_MARKER: &str = "<!-- progress:start -->";
+pub const END_MARKER: &str = "<!-- progress:end -->";
+
+/// How many days an event has, whether or not they're implemented yet.
+const DAYS: usize = 25;
+
+/// How settled one part of a day is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Star {
+    /// The answer matches the one in the store.
+    Solved,
+    /// The store has an answer, but nothing was run to check it.
+    Recorded,
+    /// There's an answer but nothing in the store to check it against.
+    Unverified,
+    /// The answer differs from the store, or the solution no longer produces one.
+    Wrong,
+    Unsolved,
+}
+
+impl Star {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Star::Solved => "★",
+            Star::Recorded => "✓",
+            Star::Unverified => "☆",
+            Star::Wrong => "✗",
+            Star::Unsolved => "·",
+        }
+    }
+
+    fn judge(answer: Option<i64>, stored: Option<i64>) -> Self {
+        match (answer, stored) {
+            (Some(answer), Some(stored)) if answer == stored => Star::Solved,
+            (Some(_), None) => Star::Unverified,
+            (None, None) => Star::Unsolved,
+            _ => Star::Wrong,
+        }
+    }
+}
+
+/// One row of the progress table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayProgress {
+    pub year: usize,
+    pub day: usize,
+    pub answers: [Option<i64>; 2],
+    pub stars: [Star; 2],
+    pub medians: [Option<Duration>; 2],
+}
+
+/// Builds the progress of every day, either by running each one `runs` times or, if `runs` is
+/// zero, from the answers store alone.
+pub fn progress(days: &[Factory], options: &Options, runs: usize) -> io::Result<Vec<DayProgress>> {
+    let mut result = vec![];
+    let mut stores: Vec<(usize, AnswerStore)> = vec![];
+
+    for factory in days {
+        let (year, day) = factory().name();
+        if !stores.iter().any(|(y, _)| *y == year) {
+            stores.push((year, AnswerStore::load(store_path(year))?));
+        }
+        let store = &stores.iter().find(|(y, _)| *y == year).unwrap().1;
+        let stored = [store.get(day, 1), store.get(day, 2)];
+
+        if runs == 0 {
+            let stars = stored.map(|answer| if answer.is_some() { Star::Recorded } else { Star::Unsolved });
+            result.push(DayProgress { year, day, answers: stored, stars, medians: [None, None] });
+            continue;
+        }
+
+        let mut answers = [None, None];
+        let mut timings: [Vec<Duration>; 2] = [vec![], vec![]];
+        for _ in 0..runs {
+            for report in run_solution_observed(factory(), options, &mut Silent) {
+                let part = match report.phase {
+                    Phase::Parse => continue,
+                    Phase::Part1 => 0,
+                    Phase::Part2 => 1,
+                };
+                if let Outcome::Answer(answer) = report.outcome {
+                    answers[part] = Some(answer);
+                    timings[part].push(report.duration);
+                }
+            }
+        }
+
+        let stars = [Star::judge(answers[0], stored[0]), Star::judge(answers[1], stored[1])];
+        let medians = timings.map(|mut t| {
+            t.sort();
+            t.get(t.len() / 2).copied()
+        });
+        result.push(DayProgress { year, day, answers, stars, medians });
+    }
+
+    Ok(result)
+}
+
+struct Silent;
+
+impl Observer for Silent {}
+
+/// The markdown for the progress section, markers included. Answers are only shown if `reveal`
+/// is set.
+pub fn section(days: &[DayProgress], reveal: bool) -> String {
+    let stars = days.iter().flat_map(|d| d.stars).filter(|&s| matches!(s, Star::Solved | Star::Recorded)).count();
+    let mut text = format!("{START_MARKER}\n## Progress\n\n**{stars}/{} stars**\n\n", DAYS * 2);
+    text.push_str(&calendar(days));
+
+    text.push_str("| Day | Part 1 | Part 2 |");
+    if reveal {
+        text.push_str(" Answer 1 | Answer 2 |");
+    }
+    text.push_str(" Median 1 | Median 2 | Source |\n|----:|:------:|:------:|");
+    if reveal {
+        text.push_str("---------:|---------:|");
+    }
+    text.push_str("---------:|---------:|--------|\n");
+
+    for d in days {
+        text.push_str(&format!("| {} | {} | {} |", d.day, d.stars[0].symbol(), d.stars[1].symbol()));
+        if reveal {
+            let answer = |a: Option<i64>| a.map_or("".to_string(), |a| a.to_string());
+            text.push_str(&format!(" {} | {} |", answer(d.answers[0]), answer(d.answers[1])));
+        }
+
+        let median = |m: Option<Duration>| m.map_or("–".to_string(), duration_string);
+        let source = format!("crates/aoc{0}/src/aoc{0}_{1:02}.rs", d.year, d.day);
+        text.push_str(&format!(
+            " {} | {} | [aoc{}_{:02}.rs]({source}) |\n",
+            median(d.medians[0]),
+            median(d.medians[1]),
+            d.year,
+            d.day
+        ));
+    }
+
+    text.push_str("\n★ matches the recorded answer, ✓ is recorded but wasn't run, ☆ has no recorded answer yet, ✗ doesn't match it.\n");
+    text.push_str(END_MARKER);
+    text.push('\n');
+    text
+}
+
+/// The 25 days of the event as a five by five grid of stars.
+fn calendar(days: &[DayProgress]) -> String {
+    let mut text = String::from("|   |   |   |   |   |\n|---|---|---|---|---|\n");
+
+    for row in 0..5 {
+        text.push('|');
+        for day in row * 5 + 1..=row * 5 + 5 {
+            let stars = days.iter().find(|d| d.day == day).map_or("··".to_string(), |d| {
+                d.stars.iter().map(|star| star.symbol()).collect()
+            });
+            text.push_str(&format!(" {day} {stars} |"));
+        }
+        text.push('\n');
+    }
+
+    text.push('\n');
+    text
+}
+
+fn duration_string(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1000 {
+        format!("{micros} µs")
+    } else if micros < 1_000_000 {
+        format!("{:.2} ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+/// Replaces the marked section of `readme` with `section`, or appends it if there isn't one. The
+/// result keeps the line endings `readme` uses.
+pub fn splice(readme: &str, section: &str) -> String {
+    let crlf = readme.contains("\r\n");
+    let readme = readme.replace("\r\n", "\n");
+
+    let result = match (readme.find(START_MARKER), readme.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => {
+            let mut rest = &readme[end + END_MARKER.len()..];
+            rest = rest.strip_prefix('\n').unwrap_or(rest);
+            format!("{}{section}{rest}", &readme[..start])
+        }
+        _ => {
+            let separator = if readme.is_empty() { "" } else if readme.ends_with('\n') { "\n" } else { "\n\n" };
+            format!("{readme}{separator}{section}")
+        }
+    };
+
+    if crlf {
+        result.replace('\n', "\r\n")
+    } else {
+        result
+    }
+}
+
+/// Regenerates the progress section of the repository's `README.md`.
+pub fn generate(days: &[Factory], options: &Options) -> io::Result<()> {
+    let path = get_repo_root().join("aoc/README.md");
+    let runs = if options.readme.from_store { 0 } else { options.readme.runs.max(1) };
+
+    let progress = progress(days, options, runs)?;
+    let readme = read_to_string(&path).unwrap_or_default();
+    write(&path, splice(&readme, &section(&progress, options.readme.reveal)))?;
+
+    println!("Updated {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days() -> Vec<DayProgress> {
+        vec![
+            DayProgress {
+                year: 2023,
+                day: 1,
+                answers: [Some(142), Some(281)],
+                stars: [Star::Solved, Star::Unverified],
+                medians: [Some(Duration::from_micros(90)), Some(Duration::from_micros(1500))],
+            },
+            DayProgress { year: 2023, day: 2, answers: [None, None], stars: [Star::Unsolved; 2], medians: [None; 2] },
+        ]
+    }
+
+    #[test]
+    fn judges_answers() {
+        assert_eq!(Star::Solved, Star::judge(Some(1), Some(1)));
+        assert_eq!(Star::Wrong, Star::judge(Some(2), Some(1)));
+        assert_eq!(Star::Wrong, Star::judge(None, Some(1)));
+        assert_eq!(Star::Unverified, Star::judge(Some(2), None));
+        assert_eq!(Star::Unsolved, Star::judge(None, None));
+    }
+
+    #[test]
+    fn redacts_answers_by_default() {
+        let hidden = section(&days(), false);
+        assert!(hidden.contains("**1/50 stars**"));
+        assert!(hidden.contains("| 1 | ★ | ☆ | 90 µs | 1.50 ms | [aoc2023_01.rs](crates/aoc2023/src/aoc2023_01.rs) |"));
+        assert!(!hidden.contains("142"));
+
+        let shown = section(&days(), true);
+        assert!(shown.contains("| 1 | ★ | ☆ | 142 | 281 | 90 µs |"));
+        assert!(shown.contains("| 2 | · | · |  |  | – | – |"));
+    }
+
+    #[test]
+    fn counts_recorded_stars() {
+        let mut days = days();
+        days[1].stars = [Star::Recorded, Star::Unsolved];
+
+        let section = section(&days, false);
+        assert!(section.contains("**2/50 stars**"));
+        assert!(section.contains("| 2 | ✓ | · |"));
+    }
+
+    #[test]
+    fn draws_calendar() {
+        let calendar = calendar(&days());
+        assert_eq!(Some("| 1 ★☆ | 2 ·· | 3 ·· | 4 ·· | 5 ·· |"), calendar.lines().nth(2));
+        assert_eq!(Some("| 21 ·· | 22 ·· | 23 ·· | 24 ·· | 25 ·· |"), calendar.lines().nth(6));
+    }
+
+    #[test]
+    fn splices_idempotently() {
+        let section = section(&days(), false);
+        let readme = "#Advent Of Code\r\n\r\nAdvent of Code in Rust";
+
+        let once = splice(readme, &section);
+        assert!(once.starts_with("#Advent Of Code\r\n\r\nAdvent of Code in Rust\r\n\r\n<!-- progress:start -->\r\n"));
+        assert_eq!(once, splice(&once, &section));
+
+        let edited = format!("{once}\r\nMore notes\r\n");
+        let again = splice(&edited, &section);
+        assert_eq!(edited, again);
+        assert_eq!(1, again.matches(START_MARKER).count());
+    }
+}
@@ -0,0 +1,72 @@
+use std::fs::{read_to_string, write};
+use std::io;
+use std::path::Path;
+
+/// Reads the file at `path` as one record per line of whitespace separated fields, skipping
+/// blank lines and `#` comments. A file that doesn't exist yet has no records. `parse` turns the
+/// fields of a line into a record, and a line it rejects fails the load with `format`, the
+/// fields that were expected, in the message.
+pub fn load<T, F>(path: &Path, format: &str, parse: F) -> io::Result<Vec<T>>
+where
+    F: Fn(&[&str]) -> Option<T>,
+{
+    let text = match read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error),
+    };
+
+    let mut records = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        records.push(parse(&fields).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}:{}: expected {format}", path.display(), i + 1))
+        })?);
+    }
+
+    Ok(records)
+}
+
+/// Writes `lines` to `path` after a `# header` comment naming the fields.
+pub fn save<I: IntoIterator<Item = String>>(path: &Path, header: &str, lines: I) -> io::Result<()> {
+    let mut text = format!("# {header}\n");
+    for line in lines {
+        text.push_str(&line);
+        text.push('\n');
+    }
+    write(path, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempPath;
+
+    fn pair(fields: &[&str]) -> Option<(usize, String)> {
+        match fields {
+            [number, word] => Some((number.parse().ok()?, word.to_string())),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let path = TempPath::new("records.txt");
+        assert!(load(&path, "<number> <word>", pair).unwrap().is_empty());
+
+        save(&path, "number word", ["1 one".to_string(), "2 two".to_string()]).unwrap();
+        assert_eq!("# number word\n1 one\n2 two\n", read_to_string(&path).unwrap());
+
+        write(&path, "# comment\n\n  3 three  \n").unwrap();
+        assert_eq!(vec![(3, "three".to_string())], load(&path, "<number> <word>", pair).unwrap());
+
+        write(&path, "1 one\nthree\n").unwrap();
+        let error = load(&path, "<number> <word>", pair).unwrap_err();
+        assert!(error.to_string().ends_with(":2: expected <number> <word>"));
+    }
+}
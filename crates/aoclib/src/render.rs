@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use crate::grid::GridCell;
+use crate::visualize::{Colour, Frame};
+
+/// Pixels per grid cell in exported images.
+pub const EXPORT_SCALE: usize = 4;
+
+/// Colours for a grid's cell type, shared by the terminal and image renderers.
+pub trait Palette: GridCell {
+    fn colour(&self) -> Colour;
+}
+
+impl Colour {
+    pub fn rgb(&self) -> [u8; 3] {
+        match self {
+            Colour::Default => [0, 0, 0],
+            Colour::Black => [0, 0, 0],
+            Colour::Red => [205, 49, 49],
+            Colour::Green => [13, 188, 121],
+            Colour::Yellow => [229, 229, 16],
+            Colour::Blue => [36, 114, 200],
+            Colour::Magenta => [188, 63, 188],
+            Colour::Cyan => [17, 168, 205],
+            Colour::White => [229, 229, 229],
+            Colour::Grey => [102, 102, 102],
+            Colour::Rgb(r, g, b) => [*r, *g, *b],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RenderError {
+    Io(std::io::Error),
+    Empty,
+    TooLarge,
+    TooManyColours,
+    SizeMismatch,
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::Io(error) => write!(f, "{error}"),
+            RenderError::Empty => write!(f, "nothing to render"),
+            RenderError::TooLarge => write!(f, "image is too large for the format"),
+            RenderError::TooManyColours => write!(f, "a GIF can't hold more than 256 colours"),
+            RenderError::SizeMismatch => write!(f, "every frame of an animation must be the same size"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl From<std::io::Error> for RenderError {
+    fn from(error: std::io::Error) -> Self {
+        RenderError::Io(error)
+    }
+}
+
+/// An RGB bitmap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    /// Draws each cell of `frame` as a `scale` by `scale` square of its colour. Rows shorter than
+    /// the widest one are padded with black.
+    pub fn from_frame(frame: &Frame, scale: usize) -> Self {
+        let columns = frame.cells.iter().map(Vec::len).max().unwrap_or(0);
+        let width = columns * scale;
+        let height = frame.cells.len() * scale;
+        let mut pixels = vec![[0; 3]; width * height];
+
+        for (row, cells) in frame.cells.iter().enumerate() {
+            for (col, (_, colour)) in cells.iter().enumerate() {
+                let rgb = colour.rgb();
+                for y in row * scale..(row + 1) * scale {
+                    pixels[y * width + col * scale..y * width + (col + 1) * scale].fill(rgb);
+                }
+            }
+        }
+
+        Image { width, height, pixels }
+    }
+
+    pub fn from_grid<T: Palette>(grid: &[Vec<T>], scale: usize) -> Self {
+        Image::from_frame(&Frame::from_grid("", grid, T::colour), scale)
+    }
+}
+
+pub fn write_png<P: AsRef<Path>>(path: P, image: &Image) -> Result<(), RenderError> {
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(&encode_png(image)?)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Writes `images` as a looping GIF animation, showing each for `delay_cs` hundredths of a second.
+pub fn write_gif<P: AsRef<Path>>(path: P, images: &[Image], delay_cs: u16) -> Result<(), RenderError> {
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(&encode_gif(images, delay_cs)?)?;
+    out.flush()?;
+    Ok(())
+}
+
+pub fn encode_png(image: &Image) -> Result<Vec<u8>, RenderError> {
+    if image.width == 0 || image.height == 0 {
+        return Err(RenderError::Empty);
+    }
+    let width = u32::try_from(image.width).map_err(|_| RenderError::TooLarge)?;
+    let height = u32::try_from(image.height).map_err(|_| RenderError::TooLarge)?;
+
+    let mut header = vec![];
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bit RGB, default compression and filtering, not interlaced
+    header.extend([8, 2, 0, 0, 0]);
+
+    // Every scanline starts with filter type 0 (none)
+    let mut raw = Vec::with_capacity(image.height * (image.width * 3 + 1));
+    for row in image.pixels.chunks(image.width) {
+        raw.push(0);
+        row.iter().for_each(|pixel| raw.extend(pixel));
+    }
+
+    let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    Ok(png)
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut result = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+
+    if blocks.peek().is_none() {
+        result.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        result.push(last as u8);
+        result.extend(len.to_le_bytes());
+        result.extend((!len).to_le_bytes());
+        result.extend(block);
+    }
+
+    result.extend(adler32(data).to_be_bytes());
+    result
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+pub fn encode_gif(images: &[Image], delay_cs: u16) -> Result<Vec<u8>, RenderError> {
+    let first = images.first().ok_or(RenderError::Empty)?;
+    let width = u16::try_from(first.width).map_err(|_| RenderError::TooLarge)?;
+    let height = u16::try_from(first.height).map_err(|_| RenderError::TooLarge)?;
+    if width == 0 || height == 0 {
+        return Err(RenderError::Empty);
+    }
+
+    // One global colour table shared by every frame
+    let mut palette: Vec<[u8; 3]> = vec![];
+    let mut indices: HashMap<[u8; 3], u8> = HashMap::new();
+    for pixel in images.iter().flat_map(|image| &image.pixels) {
+        if !indices.contains_key(pixel) {
+            if palette.len() == 256 {
+                return Err(RenderError::TooManyColours);
+            }
+            indices.insert(*pixel, palette.len() as u8);
+            palette.push(*pixel);
+        }
+    }
+
+    let table_bits = (usize::BITS - (palette.len().max(2) - 1).leading_zeros()) as u8;
+    palette.resize(1 << table_bits, [0; 3]);
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(width.to_le_bytes());
+    gif.extend(height.to_le_bytes());
+    gif.extend([0x80 | 0x70 | (table_bits - 1), 0, 0]);
+    palette.iter().for_each(|colour| gif.extend(colour));
+
+    // Loop forever
+    gif.extend([0x21, 0xff, 0x0b]);
+    gif.extend(b"NETSCAPE2.0");
+    gif.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    for image in images {
+        if image.width != first.width || image.height != first.height {
+            return Err(RenderError::SizeMismatch);
+        }
+
+        gif.extend([0x21, 0xf9, 0x04, 0x00]);
+        gif.extend(delay_cs.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend(width.to_le_bytes());
+        gif.extend(height.to_le_bytes());
+        gif.push(0);
+
+        let min_code_size = table_bits.max(2);
+        let data = lzw_encode(image.pixels.iter().map(|pixel| indices[pixel]), min_code_size);
+        gif.push(min_code_size);
+        for block in data.chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+
+    gif.push(0x3b);
+    Ok(gif)
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn lzw_encode<I: Iterator<Item = u8>>(indices: I, min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter { bytes: vec![], buffer: 0, bits: 0 };
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size + 1;
+    let mut next_code = end + 1;
+    let mut current: Option<u16> = None;
+
+    writer.write(clear, code_size);
+
+    for index in indices {
+        let Some(prefix) = current else {
+            current = Some(index as u16);
+            continue;
+        };
+
+        if let Some(&code) = dictionary.get(&(prefix, index)) {
+            current = Some(code);
+            continue;
+        }
+
+        writer.write(prefix, code_size);
+        if next_code < 4096 {
+            dictionary.insert((prefix, index), next_code);
+            next_code += 1;
+            if next_code > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            writer.write(clear, code_size);
+            dictionary.clear();
+            code_size = min_code_size + 1;
+            next_code = end + 1;
+        }
+        current = Some(index as u16);
+    }
+
+    if let Some(prefix) = current {
+        writer.write(prefix, code_size);
+        // The decoder adds one more entry after reading the last code
+        if next_code < 4096 && next_code + 1 > 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+    }
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let (mut buffer, mut bits, mut bytes) = (0u32, 0u8, data.iter());
+        let mut result = vec![];
+
+        loop {
+            while bits < code_size {
+                buffer |= (*bytes.next().unwrap() as u32) << bits;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as usize;
+            buffer >>= code_size;
+            bits -= code_size;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).chain([vec![], vec![]]).collect();
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return result;
+            }
+
+            let entry = match (table.get(code), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => {
+                    let mut entry = table[prev].clone();
+                    entry.push(table[prev][0]);
+                    entry
+                }
+                _ => panic!("Invalid code"),
+            };
+            if let Some(prev) = previous {
+                let mut added = table[prev].clone();
+                added.push(entry[0]);
+                table.push(added);
+                if table.len() == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            result.extend(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        for (len, colours) in [(1, 2), (100, 2), (5000, 4), (20000, 256), (70000, 16)] {
+            let indices: Vec<u8> = (0..len).map(|i: usize| ((i * 7 + i / 13) % colours) as u8).collect();
+            let min_code_size = (usize::BITS - (colours - 1).leading_zeros()).max(2) as u8;
+
+            assert_eq!(indices, lzw_decode(&lzw_encode(indices.iter().copied(), min_code_size), min_code_size));
+        }
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(0xae42_6082, crc32(b"IEND"));
+        assert_eq!(0x11e6_0398, adler32(b"Wikipedia"));
+    }
+
+    #[test]
+    fn frame_to_image() {
+        let mut frame = Frame::new("test");
+        frame.push_row(vec![('#', Colour::Red), ('.', Colour::Default)]);
+        frame.push_row(vec![('#', Colour::Rgb(1, 2, 3))]);
+
+        let image = Image::from_frame(&frame, 2);
+        assert_eq!((4, 4), (image.width, image.height));
+        assert_eq!(Colour::Red.rgb(), image.pixels[5]);
+        assert_eq!([0, 0, 0], image.pixels[3]);
+        assert_eq!([1, 2, 3], image.pixels[12]);
+        assert_eq!([0, 0, 0], image.pixels[15]);
+    }
+
+    #[test]
+    fn encodes_png() {
+        let image = Image { width: 2, height: 1, pixels: vec![[255, 0, 0], [0, 0, 255]] };
+        let png = encode_png(&image).unwrap();
+
+        assert_eq!(b"\x89PNG\r\n\x1a\n", &png[..8]);
+        assert_eq!(b"IHDR", &png[12..16]);
+        assert_eq!(b"IEND", &png[png.len() - 8..png.len() - 4]);
+        assert!(matches!(encode_png(&Image { width: 0, height: 0, pixels: vec![] }), Err(RenderError::Empty)));
+    }
+
+    #[test]
+    fn encodes_gif() {
+        let image = Image { width: 2, height: 1, pixels: vec![[255, 0, 0], [0, 0, 255]] };
+        let gif = encode_gif(&[image.clone(), image], 10).unwrap();
+
+        assert_eq!(b"GIF89a", &gif[..6]);
+        assert_eq!(Some(&0x3b), gif.last());
+        assert_eq!(2, gif.windows(2).filter(|w| w == &[0x21, 0xf9]).count());
+        assert!(matches!(encode_gif(&[], 10), Err(RenderError::Empty)));
+    }
+}
@@ -0,0 +1,336 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::time::Duration;
+use crate::worker::{Outcome, Phase, PhaseReport};
+
+/// A puzzle's year and day, as returned by [`crate::Runner::name`].
+pub type Name = (usize, usize);
+
+/// Receives events as the runner works through the selected days. Every method does nothing by
+/// default, so a reporter only implements the events it cares about.
+pub trait Observer {
+    fn day_started(&mut self, _name: Name) {}
+
+    fn phase_finished(&mut self, _name: Name, _report: &PhaseReport) {}
+
+    /// Called after [`Observer::phase_finished`] for a phase that panicked or timed out.
+    fn phase_failed(&mut self, _name: Name, _report: &PhaseReport) {}
+
+    fn run_finished(&mut self) {}
+}
+
+/// Passes every event on to each observer in turn.
+impl Observer for Vec<Box<dyn Observer>> {
+    fn day_started(&mut self, name: Name) {
+        self.iter_mut().for_each(|observer| observer.day_started(name));
+    }
+
+    fn phase_finished(&mut self, name: Name, report: &PhaseReport) {
+        self.iter_mut().for_each(|observer| observer.phase_finished(name, report));
+    }
+
+    fn phase_failed(&mut self, name: Name, report: &PhaseReport) {
+        self.iter_mut().for_each(|observer| observer.phase_failed(name, report));
+    }
+
+    fn run_finished(&mut self) {
+        self.iter_mut().for_each(|observer| observer.run_finished());
+    }
+}
+
+/// The runner's usual output: a header per day and a line per phase with its timing.
+#[derive(Debug, Default)]
+pub struct ConsoleReporter;
+
+impl Observer for ConsoleReporter {
+    fn day_started(&mut self, name: Name) {
+        println!("---- {}, Day {} ----", name.0, name.1);
+    }
+
+    fn phase_finished(&mut self, _name: Name, report: &PhaseReport) {
+        let mut timing = get_duration_string(report.duration);
+        if let Some(alloc) = report.alloc {
+            timing = format!("{timing} {alloc}");
+        }
+
+        let answer = |answer: i64| match report.expected {
+            Some(expected) => {
+                let mark = if answer == expected { "✓" } else { "✗" };
+                format!("{answer} {mark} (expected {expected})")
+            }
+            None => format!("{answer}"),
+        };
+
+        match (&report.outcome, report.phase) {
+            (Outcome::Parsed, _) => println!("{timing} Parsing"),
+            (Outcome::Answer(a), Phase::Part1) => print_solution(1, &[answer(*a)], &timing),
+            (Outcome::Answer(a), _) => print_solution(2, &[answer(*a)], &timing),
+            (Outcome::Panicked(message), phase) => println!("{timing} {phase} panicked: {message}"),
+            (Outcome::TimedOut, phase) => println!("{timing} {phase} timed out"),
+            (Outcome::Skipped, phase) => println!("{:11} {phase} skipped", ""),
+        }
+    }
+}
+
+fn print_solution(which: usize, output: &[String], timing: &str) {
+
+
+    let mut i = output.iter();
+
+    println!(
+        "{} Part {which}: {}",
+        timing,
+        i.next().unwrap()
+    );
+
+    for line in i {
+        println!("{:width$}{line}", "", width = timing.len() + 5);
+    }
+}
+
+pub fn get_duration_string(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let milliseconds = duration.subsec_millis();
+    let microseconds = duration.subsec_micros() % 1000;
+
+    format!("{seconds:3}.{milliseconds:03}.{microseconds:03}")
+}
+
+/// Writes every phase as a JSON array once the run finishes.
+pub struct JsonReporter<W: Write> {
+    out: W,
+    entries: Vec<String>,
+}
+
+impl<W: Write> JsonReporter<W> {
+    pub fn new(out: W) -> Self {
+        JsonReporter { out, entries: vec![] }
+    }
+}
+
+impl<W: Write> Observer for JsonReporter<W> {
+    fn phase_finished(&mut self, name: Name, report: &PhaseReport) {
+        let (outcome, answer, message) = match &report.outcome {
+            Outcome::Parsed => ("parsed", None, None),
+            Outcome::Answer(answer) => ("answer", Some(*answer), None),
+            Outcome::Panicked(message) => ("panicked", None, Some(message.as_str())),
+            Outcome::TimedOut => ("timed_out", None, None),
+            Outcome::Skipped => ("skipped", None, None),
+        };
+
+        let mut entry = format!(
+            "{{\"year\": {}, \"day\": {}, \"phase\": {}, \"outcome\": \"{outcome}\", \"nanos\": {}",
+            name.0,
+            name.1,
+            json_string(&report.phase.to_string()),
+            report.duration.as_nanos()
+        );
+        if let Some(answer) = answer {
+            entry.push_str(&format!(", \"answer\": {answer}"));
+        }
+        if let Some(expected) = report.expected {
+            entry.push_str(&format!(", \"expected\": {expected}"));
+        }
+        if let Some(message) = message {
+            entry.push_str(&format!(", \"message\": {}", json_string(message)));
+        }
+        if let Some(alloc) = report.alloc {
+            entry.push_str(&format!(
+                ", \"allocations\": {}, \"bytes\": {}, \"peak\": {}",
+                alloc.allocations, alloc.bytes, alloc.peak
+            ));
+        }
+        entry.push('}');
+        self.entries.push(entry);
+    }
+
+    fn run_finished(&mut self) {
+        let json = format!("[\n  {}\n]\n", self.entries.join(",\n  "));
+        if let Err(error) = self.out.write_all(json.as_bytes()).and_then(|_| self.out.flush()) {
+            eprintln!("Unable to write JSON report: {error}");
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[derive(Debug, Default, Clone)]
+struct DayRow {
+    part1: String,
+    part2: String,
+    total: Duration,
+}
+
+/// Writes a markdown table with a row per day once the run finishes.
+pub struct MarkdownReporter<W: Write> {
+    out: W,
+    rows: BTreeMap<Name, DayRow>,
+}
+
+impl<W: Write> MarkdownReporter<W> {
+    pub fn new(out: W) -> Self {
+        MarkdownReporter { out, rows: BTreeMap::new() }
+    }
+}
+
+impl<W: Write> Observer for MarkdownReporter<W> {
+    fn phase_finished(&mut self, name: Name, report: &PhaseReport) {
+        let row = self.rows.entry(name).or_default();
+        row.total += report.duration;
+
+        let cell = match &report.outcome {
+            Outcome::Parsed => return,
+            Outcome::Answer(answer) => answer.to_string(),
+            Outcome::Panicked(_) => "panicked".to_string(),
+            Outcome::TimedOut => "timed out".to_string(),
+            Outcome::Skipped => "skipped".to_string(),
+        };
+        match report.phase {
+            Phase::Part1 => row.part1 = cell,
+            _ => row.part2 = cell,
+        }
+    }
+
+    fn run_finished(&mut self) {
+        let mut table = String::from("| Day | Part 1 | Part 2 | Time |\n|----:|-------:|-------:|-----:|\n");
+        for (name, row) in &self.rows {
+            table.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                name.1,
+                row.part1,
+                row.part2,
+                get_duration_string(row.total).trim()
+            ));
+        }
+
+        if let Err(error) = self.out.write_all(table.as_bytes()).and_then(|_| self.out.flush()) {
+            eprintln!("Unable to write markdown report: {error}");
+        }
+    }
+}
+
+/// Prints a summary of where the time went once the run finishes.
+#[derive(Debug, Default)]
+pub struct BenchmarkReporter {
+    phases: Vec<(Name, Phase, Duration)>,
+    failures: usize,
+}
+
+impl BenchmarkReporter {
+    pub fn summary(&self) -> String {
+        let total: Duration = self.phases.iter().map(|&(_, _, duration)| duration).sum();
+        let mut slowest = self.phases.clone();
+        slowest.sort_by_key(|&(_, _, duration)| Reverse(duration));
+
+        let mut summary = format!("---- Benchmark ----\n{} Total over {} phases\n", get_duration_string(total), self.phases.len());
+        for (name, phase, duration) in slowest.iter().take(5) {
+            let share = if total.is_zero() { 0.0 } else { 100.0 * duration.as_secs_f64() / total.as_secs_f64() };
+            summary.push_str(&format!("{} Day {:2} {phase} ({share:.1}%)\n", get_duration_string(*duration), name.1));
+        }
+        if self.failures > 0 {
+            summary.push_str(&format!("{} phases failed\n", self.failures));
+        }
+        summary
+    }
+}
+
+impl Observer for BenchmarkReporter {
+    fn phase_finished(&mut self, name: Name, report: &PhaseReport) {
+        if report.outcome != Outcome::Skipped {
+            self.phases.push((name, report.phase, report.duration));
+        }
+    }
+
+    fn phase_failed(&mut self, _name: Name, _report: &PhaseReport) {
+        self.failures += 1;
+    }
+
+    fn run_finished(&mut self) {
+        print!("{}", self.summary());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use super::*;
+
+    fn report(phase: Phase, outcome: Outcome, millis: u64) -> PhaseReport {
+        PhaseReport { phase, outcome, duration: Duration::from_millis(millis), alloc: None, expected: None }
+    }
+
+    fn run(observer: &mut dyn Observer) {
+        observer.day_started((2023, 1));
+        observer.phase_finished((2023, 1), &report(Phase::Parse, Outcome::Parsed, 1));
+        observer.phase_finished((2023, 1), &report(Phase::Part1, Outcome::Answer(42), 2));
+        let failed = report(Phase::Part2, Outcome::Panicked("bad \"input\"".to_string()), 3);
+        observer.phase_finished((2023, 1), &failed);
+        observer.phase_failed((2023, 1), &failed);
+        observer.run_finished();
+    }
+
+    #[test]
+    fn json() {
+        let mut out = vec![];
+        run(&mut JsonReporter::new(&mut out));
+
+        let json = String::from_utf8(out).unwrap();
+        assert!(json.starts_with("[\n  {\"year\": 2023, \"day\": 1, \"phase\": \"Parsing\", \"outcome\": \"parsed\", \"nanos\": 1000000}"));
+        assert!(json.contains("\"outcome\": \"answer\", \"nanos\": 2000000, \"answer\": 42}"));
+        assert!(json.contains("\"message\": \"bad \\\"input\\\"\"}\n]"));
+    }
+
+    #[test]
+    fn markdown() {
+        let mut out = vec![];
+        run(&mut MarkdownReporter::new(&mut out));
+
+        let table = String::from_utf8(out).unwrap();
+        assert_eq!(Some("| 1 | 42 | panicked | 0.006.000 |"), table.lines().nth(2));
+    }
+
+    #[test]
+    fn benchmark() {
+        let mut benchmark = BenchmarkReporter::default();
+        benchmark.phase_finished((2023, 1), &report(Phase::Part1, Outcome::Answer(1), 1));
+        benchmark.phase_finished((2023, 2), &report(Phase::Part2, Outcome::Answer(1), 3));
+        benchmark.phase_failed((2023, 2), &report(Phase::Part2, Outcome::TimedOut, 3));
+
+        let summary = benchmark.summary();
+        assert!(summary.contains("  0.004.000 Total over 2 phases"));
+        assert_eq!(Some("  0.003.000 Day  2 Part 2 (75.0%)"), summary.lines().nth(2));
+        assert!(summary.ends_with("1 phases failed\n"));
+    }
+
+    struct Counter(Rc<Cell<usize>>);
+
+    impl Observer for Counter {
+        fn phase_finished(&mut self, _name: Name, _report: &PhaseReport) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn combines_observers() {
+        let count = Rc::new(Cell::new(0));
+        let mut observers: Vec<Box<dyn Observer>> = vec![Box::new(Counter(count.clone())), Box::new(Counter(count.clone()))];
+        run(&mut observers);
+
+        assert_eq!(6, count.get());
+    }
+}
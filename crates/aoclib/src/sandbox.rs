@@ -0,0 +1,241 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+#[cfg(target_os = "linux")]
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Command, ExitStatus};
+use std::time::Duration;
+#[cfg(target_os = "linux")]
+use std::time::Instant;
+use crate::cli::Options;
+
+/// Resource limits applied to a sandboxed child with `setrlimit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Address space limit in MiB.
+    pub memory_mib: u64,
+    /// CPU time limit in seconds.
+    pub cpu_secs: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits { memory_mib: 4096, cpu_secs: 300 }
+    }
+}
+
+/// The exit code of a run where a phase panicked or timed out.
+pub const PHASE_FAILED: i32 = 3;
+
+/// How a sandboxed child finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChildStatus {
+    Completed,
+    /// The child ran to the end, but one of its phases panicked or timed out.
+    PhaseFailed,
+    Exited(i32),
+    OutOfMemory,
+    CpuLimit,
+    Signalled(i32),
+}
+
+impl ChildStatus {
+    /// Classifies an exit status. Rust aborts when an allocation fails, so an abort whose
+    /// `stderr` reports a failed allocation counts as running out of memory.
+    #[cfg(target_os = "linux")]
+    pub fn from_exit(status: ExitStatus, stderr: &str) -> Self {
+        match (status.code(), status.signal()) {
+            (Some(0), _) => ChildStatus::Completed,
+            (Some(PHASE_FAILED), _) => ChildStatus::PhaseFailed,
+            (Some(code), _) => ChildStatus::Exited(code),
+            (_, Some(libc::SIGABRT)) if stderr.contains("memory allocation of") => ChildStatus::OutOfMemory,
+            (_, Some(libc::SIGXCPU)) => ChildStatus::CpuLimit,
+            (_, Some(signal)) => ChildStatus::Signalled(signal),
+            (None, None) => ChildStatus::Exited(-1),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn from_exit(status: ExitStatus, _stderr: &str) -> Self {
+        match status.code() {
+            Some(0) => ChildStatus::Completed,
+            Some(PHASE_FAILED) => ChildStatus::PhaseFailed,
+            code => ChildStatus::Exited(code.unwrap_or(-1)),
+        }
+    }
+}
+
+impl Display for ChildStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChildStatus::Completed => write!(f, "completed"),
+            ChildStatus::PhaseFailed => write!(f, "had a phase that failed"),
+            ChildStatus::Exited(code) => write!(f, "exited with code {code}"),
+            ChildStatus::OutOfMemory => write!(f, "ran out of memory"),
+            ChildStatus::CpuLimit => write!(f, "hit the CPU time limit"),
+            #[cfg(target_os = "linux")]
+            ChildStatus::Signalled(libc::SIGKILL) => write!(f, "was killed (possibly by the OOM killer)"),
+            ChildStatus::Signalled(signal) => write!(f, "was killed by signal {signal}"),
+        }
+    }
+}
+
+/// Everything a sandboxed child produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxResult {
+    pub status: ChildStatus,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+}
+
+/// Runs `command` to completion under `limits`, capturing its output.
+#[cfg(target_os = "linux")]
+pub fn run_limited(mut command: Command, limits: Limits) -> io::Result<SandboxResult> {
+    let memory = limits.memory_mib.saturating_mul(1024 * 1024);
+    let cpu = limits.cpu_secs;
+
+    // SAFETY: the closure runs in the forked child before exec and only calls setrlimit, which is
+    // async-signal-safe.
+    unsafe {
+        command.pre_exec(move || {
+            set_limit(libc::RLIMIT_AS, memory, memory)?;
+            // SIGXCPU at the soft limit, SIGKILL a second later if that's ignored
+            set_limit(libc::RLIMIT_CPU, cpu, cpu.saturating_add(1))
+        });
+    }
+
+    let start = Instant::now();
+    let output = command.output()?;
+    let duration = start.elapsed();
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    let status = ChildStatus::from_exit(output.status, &stderr);
+    Ok(SandboxResult { status, stdout, stderr, duration })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn run_limited(_command: Command, _limits: Limits) -> io::Result<SandboxResult> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "sandboxing needs Linux resource limits"))
+}
+
+// glibc and uClibc take the resource as their own enum type, musl as a plain int
+#[cfg(all(target_os = "linux", any(target_env = "gnu", target_env = "uclibc")))]
+type Resource = libc::__rlimit_resource_t;
+#[cfg(all(target_os = "linux", not(any(target_env = "gnu", target_env = "uclibc"))))]
+type Resource = libc::c_int;
+
+#[cfg(target_os = "linux")]
+fn set_limit(resource: Resource, soft: u64, hard: u64) -> io::Result<()> {
+    let limit = libc::rlimit { rlim_cur: soft as libc::rlim_t, rlim_max: hard as libc::rlim_t };
+    // SAFETY: `limit` is a valid rlimit for the duration of the call.
+    if unsafe { libc::setrlimit(resource, &limit) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Runs `day` in a child copy of this binary under `limits`, then prints what it printed and how
+/// it finished.
+pub fn run_day(day: usize, limits: Limits, options: &Options) -> io::Result<SandboxResult> {
+    let mut command = Command::new(std::env::current_exe()?);
+    command.args(options.child_args(day));
+    run_child(command, day, limits)
+}
+
+fn run_child(command: Command, day: usize, limits: Limits) -> io::Result<SandboxResult> {
+    let result = run_limited(command, limits)?;
+    print!("{}", result.stdout);
+    eprint!("{}", result.stderr);
+    if result.status != ChildStatus::Completed {
+        println!(
+            "Day {day} {} (limits: {} MiB, {} s CPU)",
+            result.status, limits.memory_mib, limits.cpu_secs
+        );
+    }
+
+    Ok(result)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::{run_selected, Runner};
+
+    const CHILD: &str = "AOC_SANDBOX_TEST_CHILD";
+
+    struct Panicking;
+
+    impl Runner for Panicking {
+        fn name(&self) -> (usize, usize) {
+            (2023, 1)
+        }
+
+        fn set_input(&mut self, _input: &str) {}
+
+        fn parse(&mut self) {}
+
+        fn part1(&mut self) -> i64 {
+            panic!("part 1 is broken")
+        }
+
+        fn part2(&mut self) -> i64 {
+            2
+        }
+    }
+
+    #[test]
+    fn classifies_exits() {
+        let status = |raw| ExitStatus::from_raw(raw);
+
+        assert_eq!(ChildStatus::Completed, ChildStatus::from_exit(status(0), ""));
+        assert_eq!(ChildStatus::Exited(2), ChildStatus::from_exit(status(2 << 8), ""));
+        assert_eq!(ChildStatus::PhaseFailed, ChildStatus::from_exit(status(PHASE_FAILED << 8), ""));
+        assert_eq!(
+            ChildStatus::OutOfMemory,
+            ChildStatus::from_exit(status(libc::SIGABRT), "memory allocation of 1024 bytes failed")
+        );
+        assert_eq!(ChildStatus::Signalled(libc::SIGABRT), ChildStatus::from_exit(status(libc::SIGABRT), ""));
+        assert_eq!(ChildStatus::CpuLimit, ChildStatus::from_exit(status(libc::SIGXCPU), ""));
+    }
+
+    #[test]
+    fn captures_output() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo out; echo err >&2; exit 4"]);
+        let result = run_limited(command, Limits::default()).unwrap();
+
+        assert_eq!(ChildStatus::Exited(4), result.status);
+        assert_eq!("out\n", result.stdout);
+        assert_eq!("err\n", result.stderr);
+    }
+
+    #[test]
+    fn enforces_cpu_limit() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "while :; do :; done"]);
+        let result = run_limited(command, Limits { memory_mib: 256, cpu_secs: 1 }).unwrap();
+
+        assert_eq!(ChildStatus::CpuLimit, result.status);
+    }
+
+    /// Stands in for the child copy of the runner; does nothing unless `fails_panicking_day`
+    /// started it.
+    #[test]
+    fn panicking_child() {
+        if std::env::var_os(CHILD).is_some() {
+            run_selected(&[|| Box::new(Panicking)], &Options::default());
+        }
+    }
+
+    #[test]
+    fn fails_panicking_day() {
+        let mut command = Command::new(std::env::current_exe().unwrap());
+        command.args(["sandbox::tests::panicking_child", "--exact", "--nocapture"]).env(CHILD, "1");
+        let result = run_child(command, 1, Limits::default()).unwrap();
+
+        assert_eq!(ChildStatus::PhaseFailed, result.status);
+        assert!(result.stdout.contains("Part 2: 2"));
+    }
+}
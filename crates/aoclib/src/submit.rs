@@ -0,0 +1,226 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use crate::answers::{store_path, AnswerStore};
+use crate::cli::Options;
+use crate::fetch::{Client, Config, FetchError};
+use crate::guesses::{ledger_path, GuessLedger, Judgement};
+use crate::worker::{Outcome, Phase};
+use crate::{html, run_solution_with, Factory, Selector};
+
+/// What the puzzle site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way.
+    Incorrect,
+    /// Submitted too soon after the last guess.
+    Wait(Duration),
+    /// The part was already solved, or its first part wasn't.
+    AlreadySolved,
+    /// A page we didn't recognise, as text.
+    Unknown(String),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer"),
+            Verdict::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Verdict::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Verdict::Incorrect => write!(f, "That's not the right answer"),
+            Verdict::Wait(wait) => write!(f, "Answered too recently, wait {}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "That part is already solved, or isn't open yet"),
+            Verdict::Unknown(text) => write!(f, "Unrecognised response: {text}"),
+        }
+    }
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page the site answers a submission with.
+    pub fn parse(html: &str) -> Self {
+        let article = match (html.find("<article"), html.find("</article>")) {
+            (Some(start), Some(end)) if start < end => &html[start..end],
+            _ => html,
+        };
+        let text = html::text_of(article).split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Verdict::TooHigh
+            } else if text.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::Wait(wait_time(&text).unwrap_or_default())
+        } else if text.contains("solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown(text)
+        }
+    }
+}
+
+/// The `4m 32s` out of "you have 4m 32s left to wait".
+fn wait_time(text: &str) -> Option<Duration> {
+    let end = text.find(" left to wait")?;
+    let start = text[..end].rfind("have ")? + "have ".len();
+
+    let mut secs = 0;
+    for field in text[start..end].split_whitespace() {
+        let (n, unit) = field.split_at(field.find(|c: char| !c.is_ascii_digit())?);
+        let n: u64 = n.parse().ok()?;
+        secs += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+impl Client {
+    /// Submits `answer` for one part of a puzzle.
+    pub fn submit(&self, year: usize, day: usize, part: usize, answer: i64) -> Result<Verdict, FetchError> {
+        self.check_unlocked(year, day)?;
+        let (level, answer) = (part.to_string(), answer.to_string());
+        let page = self.post_form(&format!("/{year}/day/{day}/answer"), &[("level", &level), ("answer", &answer)])?;
+        Ok(Verdict::parse(&page))
+    }
+}
+
+/// Submits one part of the selected day, recording the verdict in the guess ledger and the answer
+/// in the answers store if it's right. The answer is computed by running the day unless
+/// `options.submit.answer` gives it, and isn't sent if earlier guesses rule it out unless
+/// `options.submit.force` is set. Returns whether the answer was accepted.
+pub fn run(days: &[Factory], options: &Options) -> bool {
+    if options.selector == Selector::All {
+        eprintln!("Submit one day at a time");
+        return false;
+    }
+    let day = match options.selector.registered(days.len()) {
+        Ok(selected) if !selected.is_empty() => selected[0],
+        Ok(_) => return false,
+        Err(error) => {
+            eprintln!("{error}");
+            return false;
+        }
+    };
+    let factory = days[day - 1];
+    let year = factory().name().0;
+
+    let mut store = match AnswerStore::load(store_path(year)) {
+        Ok(store) => store,
+        Err(error) => {
+            eprintln!("Unable to read the answers store: {error}");
+            return false;
+        }
+    };
+    let part = match options.submit.part.or_else(|| (1..=2).find(|&part| store.get(day, part).is_none())) {
+        Some(part) => part,
+        None => {
+            println!("Both parts of day {day} are already recorded");
+            return true;
+        }
+    };
+
+    let answer = options.submit.answer.or_else(|| {
+        let phase = if part == 1 { Phase::Part1 } else { Phase::Part2 };
+        run_solution_with(factory(), options).into_iter().find(|r| r.phase == phase).and_then(|r| match r.outcome {
+            Outcome::Answer(answer) => Some(answer),
+            _ => None,
+        })
+    });
+    let Some(answer) = answer else {
+        eprintln!("Day {day} part {part} didn't produce an answer");
+        return false;
+    };
+
+    let mut ledger = match GuessLedger::load(ledger_path(year)) {
+        Ok(ledger) => ledger,
+        Err(error) => {
+            eprintln!("Unable to read the guess ledger: {error}");
+            return false;
+        }
+    };
+    if let Some(conflict) = ledger.check(day, part, answer) {
+        eprintln!("Day {day} part {part}: {answer} can't be right, {conflict}");
+        if !options.submit.force {
+            eprintln!("Not submitting it, use --force to submit anyway");
+            return false;
+        }
+    }
+
+    let client = Client::new(Config::load());
+    match client.submit(year, day, part, answer) {
+        Ok(verdict) => {
+            println!("Day {day} part {part}: {answer}: {verdict}");
+            if let Some(judgement) = Judgement::from_verdict(&verdict) {
+                ledger.record(day, part, answer, judgement);
+                if let Err(error) = ledger.save() {
+                    eprintln!("Unable to save the guess ledger: {error}");
+                }
+            }
+            if verdict != Verdict::Correct {
+                return false;
+            }
+            store.record(day, part, answer);
+            if let Err(error) = store.save() {
+                eprintln!("Unable to save the answers store: {error}");
+            }
+            true
+        }
+        Err(error) => {
+            eprintln!("Unable to submit day {day} part {part}: {error}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::mock;
+
+    fn page(message: &str) -> String {
+        format!("<html><main>\n<article><p>{message}</p></article>\n</main></html>")
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let parse = |message: &str| Verdict::parse(&page(message));
+
+        assert_eq!(Verdict::Correct, parse("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer."));
+        assert_eq!(Verdict::TooHigh, parse("That's not the right answer; your answer is too high.  If you're stuck, ..."));
+        assert_eq!(Verdict::TooLow, parse("That's not the right answer; your answer is too low."));
+        assert_eq!(Verdict::Incorrect, parse("That's not the right answer.  If you're stuck, ..."));
+        assert_eq!(
+            Verdict::Wait(Duration::from_secs(272)),
+            parse("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait.")
+        );
+        assert_eq!(
+            Verdict::Wait(Duration::from_secs(45)),
+            parse("You gave an answer too recently; you have 45s left to wait.")
+        );
+        assert_eq!(Verdict::AlreadySolved, parse("You don't seem to be solving the right level.  Did you already complete it?"));
+        assert_eq!(Verdict::Unknown("Something else".to_string()), parse("Something <em>else</em>"));
+    }
+
+    #[test]
+    fn posts_answer() {
+        let (url, request) = mock::serve_once("200 OK", &page("That's not the right answer; your answer is too low."));
+        let config = Config { session: Some("abc123".to_string()), base_url: url, contact: None };
+
+        assert_eq!(Verdict::TooLow, Client::new(config).submit(2023, 8, 2, 42).unwrap());
+        let request = request.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/8/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc123\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=42"));
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs::{remove_dir_all, remove_file, write};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// A path in the temp directory that only this process uses, removed again when dropped so
+/// tests clean up after themselves even when they fail.
+pub struct TempPath(PathBuf);
+
+impl TempPath {
+    /// A path for `name`, with nothing there yet.
+    pub fn new(name: &str) -> Self {
+        let path = TempPath(std::env::temp_dir().join(format!("aoclib-{}-{name}", std::process::id())));
+        path.remove();
+        path
+    }
+
+    /// A file for `name` holding `contents`.
+    pub fn with_contents(name: &str, contents: &str) -> Self {
+        let path = TempPath::new(name);
+        write(&path, contents).unwrap();
+        path
+    }
+
+    fn remove(&self) {
+        if self.0.is_dir() {
+            let _ = remove_dir_all(&self.0);
+        } else {
+            let _ = remove_file(&self.0);
+        }
+    }
+}
+
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
@@ -0,0 +1,186 @@
+use std::io::{stdin, stdout, Write};
+use std::thread::sleep;
+use std::time::Duration;
+use crate::grid::GridCell;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Colour {
+    #[default]
+    Default,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+    Rgb(u8, u8, u8),
+}
+
+impl Colour {
+    fn ansi(&self) -> String {
+        match self {
+            Colour::Default => "\x1b[0m".to_string(),
+            Colour::Black => "\x1b[30m".to_string(),
+            Colour::Red => "\x1b[31m".to_string(),
+            Colour::Green => "\x1b[32m".to_string(),
+            Colour::Yellow => "\x1b[33m".to_string(),
+            Colour::Blue => "\x1b[34m".to_string(),
+            Colour::Magenta => "\x1b[35m".to_string(),
+            Colour::Cyan => "\x1b[36m".to_string(),
+            Colour::White => "\x1b[37m".to_string(),
+            Colour::Grey => "\x1b[90m".to_string(),
+            Colour::Rgb(r, g, b) => format!("\x1b[38;2;{r};{g};{b}m"),
+        }
+    }
+}
+
+/// One picture of a puzzle's state: rows of characters, each with a colour.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub title: String,
+    pub cells: Vec<Vec<(char, Colour)>>,
+}
+
+impl Frame {
+    pub fn new(title: &str) -> Self {
+        Frame { title: title.to_string(), cells: vec![] }
+    }
+
+    /// Draws every cell of `grid` with its display character and the colour `colour` picks for it.
+    pub fn from_grid<T, F>(title: &str, grid: &[Vec<T>], colour: F) -> Self
+    where
+        T: GridCell,
+        F: Fn(&T) -> Colour,
+    {
+        Frame {
+            title: title.to_string(),
+            cells: grid
+                .iter()
+                .map(|row| row.iter().map(|cell| (cell.display_char(), colour(cell))).collect())
+                .collect(),
+        }
+    }
+
+    pub fn push_row(&mut self, row: Vec<(char, Colour)>) {
+        self.cells.push(row);
+    }
+
+    /// The frame as text with ANSI colour codes, only switching colour where it changes.
+    pub fn to_ansi(&self) -> String {
+        let mut result = String::new();
+
+        for row in &self.cells {
+            let mut current = Colour::Default;
+            for &(c, colour) in row {
+                if colour != current {
+                    result.push_str(&colour.ansi());
+                    current = colour;
+                }
+                result.push(c);
+            }
+            if current != Colour::Default {
+                result.push_str(&Colour::Default.ansi());
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+}
+
+/// Collects the frames a solution emits. It starts disabled, and solutions should check
+/// [`Frames::is_enabled`] before building a frame so visualisation costs nothing when it's off.
+#[derive(Debug, Default)]
+pub struct Frames {
+    enabled: bool,
+    frames: Vec<Frame>,
+}
+
+impl Frames {
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records `frame` if frames are enabled.
+    pub fn emit(&mut self, frame: Frame) {
+        if self.enabled {
+            self.frames.push(frame);
+        }
+    }
+
+    pub fn take(&mut self) -> Vec<Frame> {
+        std::mem::take(&mut self.frames)
+    }
+}
+
+/// Implemented by solutions that can draw their state while solving.
+pub trait Visualize {
+    fn frames(&mut self) -> &mut Frames;
+}
+
+/// Plays frames in the terminal, either with a fixed delay or one frame per Enter press.
+#[derive(Debug, Clone, Copy)]
+pub struct TerminalRenderer {
+    pub delay: Duration,
+    pub step: bool,
+}
+
+impl Default for TerminalRenderer {
+    fn default() -> Self {
+        TerminalRenderer { delay: Duration::from_millis(100), step: false }
+    }
+}
+
+impl TerminalRenderer {
+    pub fn render(&self, frames: &[Frame]) {
+        for (i, frame) in frames.iter().enumerate() {
+            let mut out = stdout().lock();
+            // Clear the screen and move the cursor home before each frame
+            write!(out, "\x1b[2J\x1b[H{}", frame.to_ansi()).unwrap();
+            writeln!(out, "{} [{}/{}]", frame.title, i + 1, frames.len()).unwrap();
+            out.flush().unwrap();
+            drop(out);
+
+            if self.step {
+                let mut line = String::new();
+                stdin().read_line(&mut line).unwrap();
+            } else {
+                sleep(self.delay);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_only_record_when_enabled() {
+        let mut frames = Frames::default();
+
+        frames.emit(Frame::new("zero"));
+        assert!(frames.take().is_empty());
+
+        frames.enable();
+        frames.emit(Frame::new("one"));
+        assert_eq!(vec![Frame::new("one")], frames.take());
+        assert!(frames.take().is_empty());
+    }
+
+    #[test]
+    fn ansi_output() {
+        let mut frame = Frame::new("test");
+        frame.push_row(vec![('a', Colour::Red), ('b', Colour::Red), ('c', Colour::Default)]);
+        frame.push_row(vec![('d', Colour::Default), ('e', Colour::Rgb(1, 2, 3))]);
+
+        assert_eq!("\x1b[31mab\x1b[0mc\nd\x1b[38;2;1;2;3me\x1b[0m\n", frame.to_ansi());
+    }
+}
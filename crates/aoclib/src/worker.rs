@@ -0,0 +1,201 @@
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::alloc::{self, AllocStats, Measurement};
+use crate::dot::Dot;
+use crate::visualize::Frame;
+use crate::Runner;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "Parsing"),
+            Phase::Part1 => write!(f, "Part 1"),
+            Phase::Part2 => write!(f, "Part 2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Parsed,
+    Answer(i64),
+    Panicked(String),
+    TimedOut,
+    /// Not run because parsing failed or an earlier phase never finished.
+    Skipped,
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Panicked(_) | Outcome::TimedOut)
+    }
+}
+
+/// How one phase of a solution went. `duration` is the timeout for phases that timed out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseReport {
+    pub phase: Phase,
+    pub outcome: Outcome,
+    pub duration: Duration,
+    /// What the phase allocated, if the counting allocator is installed.
+    pub alloc: Option<AllocStats>,
+    /// The answer the example should give, when running against one.
+    pub expected: Option<i64>,
+}
+
+/// What the worker hands back after each phase, along with anything the solution drew.
+pub(crate) struct Finished {
+    pub report: PhaseReport,
+    pub frames: Vec<Frame>,
+    pub graph: Option<Dot>,
+}
+
+/// Runs a solution's phases on their own thread, one at a time as they're requested, so a panic
+/// only fails that phase and a phase that never finishes can be abandoned.
+pub(crate) struct Worker {
+    commands: Sender<Phase>,
+    results: Receiver<Finished>,
+    abandoned: bool,
+}
+
+impl Worker {
+    pub fn spawn(mut solution: Box<dyn Runner + Send>, want_graph: bool) -> Self {
+        let (commands, phases) = channel::<Phase>();
+        let (finished, results) = channel();
+
+        thread::spawn(move || {
+            for phase in phases {
+                let measurement = Measurement::start();
+                let start = Instant::now();
+                let result = catch_unwind(AssertUnwindSafe(|| match phase {
+                    Phase::Parse => {
+                        solution.parse();
+                        Outcome::Parsed
+                    }
+                    Phase::Part1 => Outcome::Answer(solution.part1()),
+                    Phase::Part2 => Outcome::Answer(solution.part2()),
+                }));
+                let duration = start.elapsed();
+                let alloc = alloc::is_installed().then(|| measurement.finish());
+
+                let outcome = result.unwrap_or_else(|payload| Outcome::Panicked(panic_message(payload)));
+                let graph = match (phase, &outcome) {
+                    (Phase::Parse, Outcome::Parsed) if want_graph => solution.graph(),
+                    _ => None,
+                };
+                let frames = solution.visualize().map(|v| v.frames().take()).unwrap_or_default();
+
+                let report = PhaseReport { phase, outcome, duration, alloc, expected: None };
+                if finished.send(Finished { report, frames, graph }).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Worker { commands, results, abandoned: false }
+    }
+
+    /// Runs `phase`, waiting at most `timeout` for it. Once a phase has failed to finish in
+    /// time the worker is left to it, and every later phase is skipped. There's no way to stop
+    /// the thread, so it keeps using CPU until the process exits, and anything it allocates
+    /// shows up in the allocation counts of whatever runs next.
+    pub fn run(&mut self, phase: Phase, timeout: Option<Duration>) -> Finished {
+        let skipped = |outcome, duration| Finished {
+            report: PhaseReport { phase, outcome, duration, alloc: None, expected: None },
+            frames: vec![],
+            graph: None,
+        };
+
+        if self.abandoned || self.commands.send(phase).is_err() {
+            return skipped(Outcome::Skipped, Duration::ZERO);
+        }
+
+        let result = match timeout {
+            Some(timeout) => self.results.recv_timeout(timeout),
+            None => self.results.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match result {
+            Ok(finished) => finished,
+            Err(RecvTimeoutError::Timeout) => {
+                self.abandoned = true;
+                skipped(Outcome::TimedOut, timeout.unwrap_or_default())
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                self.abandoned = true;
+                skipped(Outcome::Panicked("worker thread stopped".to_string()), Duration::ZERO)
+            }
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Faulty {
+        parsed: bool,
+    }
+
+    impl Runner for Faulty {
+        fn name(&self) -> (usize, usize) {
+            (2000, 1)
+        }
+
+        fn set_input(&mut self, _input: &str) {}
+
+        fn parse(&mut self) {
+            self.parsed = true;
+        }
+
+        fn part1(&mut self) -> i64 {
+            panic!("No new line of reflection found")
+        }
+
+        fn part2(&mut self) -> i64 {
+            thread::sleep(Duration::from_secs(60));
+            0
+        }
+    }
+
+    #[test]
+    fn isolates_panics_and_timeouts() {
+        let mut worker = Worker::spawn(Box::new(Faulty::default()), false);
+        let timeout = Some(Duration::from_millis(50));
+
+        assert_eq!(Outcome::Parsed, worker.run(Phase::Parse, timeout).report.outcome);
+        assert_eq!(
+            Outcome::Panicked("No new line of reflection found".to_string()),
+            worker.run(Phase::Part1, timeout).report.outcome
+        );
+
+        let report = worker.run(Phase::Part2, timeout).report;
+        assert_eq!(Outcome::TimedOut, report.outcome);
+        assert_eq!(Duration::from_millis(50), report.duration);
+        assert_eq!(Outcome::Skipped, worker.run(Phase::Part2, timeout).report.outcome);
+    }
+}
@@ -0,0 +1,107 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitChar, Token};
+
+/// Derives `aoclib::grid::GridCell`, `TryFrom<char>` and `Display` for a fieldless enum.
+///
+/// Every variant needs a `#[cell('c')]` attribute naming the character it's parsed from. An
+/// optional `display = 'd'` draws it with a different character, e.g. `#[cell('|', display = '│')]`.
+#[proc_macro_derive(GridCell, attributes(cell))]
+pub fn derive_grid_cell(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct Cell {
+    variant: syn::Ident,
+    parse: LitChar,
+    display: LitChar,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(&input.ident, "GridCell can only be derived for enums"));
+    };
+
+    let cells = data.variants
+        .iter()
+        .map(|variant| {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::new_spanned(variant, "GridCell variants can't have fields"));
+            }
+
+            let attr = variant.attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("cell"))
+                .ok_or_else(|| Error::new_spanned(variant, "missing #[cell('c')] attribute"))?;
+
+            let (parse, display) = attr.parse_args_with(parse_cell_args)?;
+
+            Ok(Cell { variant: variant.ident.clone(), display: display.unwrap_or_else(|| parse.clone()), parse })
+        })
+        .collect::<Result<Vec<Cell>, Error>>()?;
+
+    let variants = cells.iter().map(|cell| &cell.variant).collect::<Vec<_>>();
+    let parse_chars = cells.iter().map(|cell| &cell.parse).collect::<Vec<_>>();
+    let display_chars = cells.iter().map(|cell| &cell.display).collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl ::aoclib::grid::GridCell for #name {
+            const ALL: &'static [Self] = &[#(#name::#variants),*];
+
+            fn from_char(c: char) -> ::std::option::Option<Self> {
+                match c {
+                    #(#parse_chars => ::std::option::Option::Some(#name::#variants),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn to_char(&self) -> char {
+                match self {
+                    #(#name::#variants => #parse_chars,)*
+                }
+            }
+
+            fn display_char(&self) -> char {
+                match self {
+                    #(#name::#variants => #display_chars,)*
+                }
+            }
+        }
+
+        impl ::std::convert::TryFrom<char> for #name {
+            type Error = ::aoclib::grid::UnknownCell;
+
+            fn try_from(c: char) -> ::std::result::Result<Self, Self::Error> {
+                <#name as ::aoclib::grid::GridCell>::from_char(c).ok_or(::aoclib::grid::UnknownCell(c))
+            }
+        }
+
+        impl ::std::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(f, "{}", <#name as ::aoclib::grid::GridCell>::display_char(self))
+            }
+        }
+    })
+}
+
+fn parse_cell_args(input: syn::parse::ParseStream) -> syn::Result<(LitChar, Option<LitChar>)> {
+    let parse: LitChar = input.parse()?;
+    let mut display = None;
+
+    if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+        let key: syn::Ident = input.parse()?;
+        if key != "display" {
+            return Err(Error::new_spanned(key, "expected `display = 'c'`"));
+        }
+        input.parse::<Token![=]>()?;
+        display = Some(input.parse()?);
+    }
+
+    Ok((parse, display))
+}